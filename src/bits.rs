@@ -325,10 +325,7 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.idx == 0 {
-            self.buf = match self.buffer() {
-                Some(b) => b,
-                None => return None,
-            };
+            self.buf = self.buffer()?;
         }
 
         // Extract MSBs.
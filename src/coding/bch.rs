@@ -1,4 +1,5 @@
-//! Encoding and decoding of the (63, 16, 23) BCH code described by P25.
+//! Encoding and decoding of the (63, 16, 23) BCH code described by P25 and its (64, 16,
+//! 24) extension with an overall parity bit.
 //!
 //! These algorithms are derived from *Coding Theory and Cryptography: The Essentials*,
 //! Hankerson, Hoffman, et al, 2000.
@@ -8,7 +9,9 @@ use std;
 use binfield_matrix::matrix_mul_systematic;
 
 use crate::coding::bmcf;
+use crate::coding::correction::{bit_corrections, Correction};
 use crate::coding::galois::{GaloisField, P25Codeword, P25Field, Polynomial, PolynomialCoefs};
+use crate::error::DecodeError;
 
 /// Encode the given 16 data bits into a 64-bit codeword.
 pub fn encode(word: u16) -> u64 {
@@ -18,25 +21,80 @@ pub fn encode(word: u16) -> u64 {
 /// Try to decode the given 64-bit word to the nearest codeword, correcting up to 11
 /// bit errors.
 ///
-/// If decoding was successful, return `Some((data, err))`, where `data` is the 16 data
-/// bits and `err` is the number of bits corrected. Otherwise, return `None` to indicate
-/// an unrecoverable error.
-pub fn decode(bits: u64) -> Option<(u16, usize)> {
+/// If decoding was successful, return `Ok((data, err))`, where `data` is the 16 data
+/// bits and `err` is the number of bits corrected. Otherwise, return an `Err` describing
+/// the unrecoverable error.
+pub fn decode(bits: u64) -> Result<(u16, usize), DecodeError> {
+    decode_detailed(bits).map(|(data, fixes)| (data, fixes.len()))
+}
+
+/// Try to decode the given 64-bit word to the nearest codeword, correcting up to 11
+/// bit errors.
+///
+/// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 16 data
+/// bits and `fixes` describes each corrected bit, indexed from the MSB of the 64-bit
+/// word. Otherwise, return an `Err` describing the unrecoverable error.
+pub fn decode_detailed(bits: u64) -> Result<(u16, Vec<Correction>), DecodeError> {
     // The BCH code is only over the first 63 bits, so strip off the P25 parity bit.
     let word = bits >> 1;
 
-    bmcf::Errors::new(syndromes(word)).map(|(nerr, errs)| {
+    bmcf::Errors::new(syndromes(word)).map(|(_, errs)| {
         // Flip all error bits.
-        let fixed = errs.fold(word, |w, (loc, pat)| {
+        let pattern = errs.fold(0, |p, (loc, pat)| {
             assert!(pat.power().unwrap() == 0);
-            w ^ 1 << loc
+            p | 1 << loc
         });
 
         // Strip off the parity bits.
-        ((fixed >> 47) as u16, nerr)
+        (
+            ((word ^ pattern) >> 47) as u16,
+            bit_corrections(pattern << 1, 64),
+        )
     })
 }
 
+/// Encode the given 16 data bits into a 64-bit codeword of the (64, 16, 24) extended
+/// code, where the last bit is the overall parity of the 63-bit BCH codeword.
+///
+/// Note that this differs from the P25 parity bit produced by `encode`, which is set
+/// only for the LDU1 and LDU2 data unit IDs.
+pub fn encode_extended(word: u16) -> u64 {
+    let bch = encode(word) & !1;
+    bch | (bch.count_ones() & 1) as u64
+}
+
+/// Try to decode the given 64-bit word of the (64, 16, 24) extended code, correcting up
+/// to 11 bit errors and detecting 12.
+///
+/// After the BCH codeword is corrected, the overall parity of all 64 bits is checked. If
+/// it fails after fewer than 11 corrections, the parity bit itself is taken to be in
+/// error and counted as a corrected bit. If it fails after 11 corrections, at least 12
+/// errors occurred and `Err(DecodeError::DetectedUncorrectable)` is returned rather than
+/// possibly wrong data.
+///
+/// If decoding was successful, return `Ok((data, err))`, where `data` is the 16 data
+/// bits and `err` is the number of bits corrected. Otherwise, return an `Err` describing
+/// the unrecoverable error.
+pub fn decode_extended(bits: u64) -> Result<(u16, usize), DecodeError> {
+    let (data, err) = decode(bits)?;
+
+    // The corrected word has odd weight exactly when the BCH corrections and the
+    // received parity bit disagree.
+    if (bits.count_ones() as usize + err) & 1 == 0 {
+        Ok((data, err))
+    } else if err < BchCoefs::errors() {
+        Ok((data, err + 1))
+    } else {
+        Err(DecodeError::DetectedUncorrectable)
+    }
+}
+
+impl_block_code! {
+    /// The (64, 16, 23) code protecting the network ID: a (63, 16, 23) BCH codeword
+    /// followed by the P25 parity bit, which isn't checked.
+    Code: 64, 16, 23, u16, u64, encode, decode
+}
+
 /// Generator matrix from P25, transformed for more efficient codeword generation.
 const GEN: &[u16] = &[
     0b1110110001000111,
@@ -257,6 +315,21 @@ mod test {
         assert_eq!(encode(0b1111) & 1, 0);
     }
 
+    #[test]
+    fn test_encode_extended() {
+        for data in [0, 0b1111111100000000, 0b1010110000111101, 0xffff] {
+            let word = encode_extended(data);
+            assert_eq!(word >> 1, encode(data) >> 1);
+            assert_eq!(word.count_ones() % 2, 0);
+        }
+
+        // The extended code has minimum distance 24.
+        assert_eq!(
+            (1..=!0u16).map(|d| encode_extended(d).count_ones()).min(),
+            Some(24)
+        );
+    }
+
     #[test]
     fn test_syndromes() {
         let w = encode(0b1111111100000000) >> 1;
@@ -298,12 +371,59 @@ mod test {
                 == (0b0000111110000000, 11)
         );
 
-        assert!(decode(encode(0b0000111110001010) ^ 0b1111111111110).is_none());
-        assert!(decode(encode(0b0000001111111111) ^ 0b11111111111111111111110).is_none());
+        assert!(decode(encode(0b0000111110001010) ^ 0b1111111111110).is_err());
+        assert!(decode(encode(0b0000001111111111) ^ 0b11111111111111111111110).is_err());
         assert!(decode(
             encode(0b0000001111111111) ^ 0b00100101010101000010001100100010011111111110
         )
-        .is_none());
+        .is_err());
+
+        assert_eq!(
+            decode_detailed(encode(0b1010101010101010) ^ (1 << 63 | 1 << 10 | 1))
+                .unwrap()
+                .1
+                .iter()
+                .map(|c| c.index)
+                .collect::<Vec<_>>(),
+            vec![0, 53]
+        );
+
+        // Corrupt a codeword with 12 bits of a minimum-weight codeword so BCH decoding
+        // miscorrects to the wrong data.
+        let diff = (1..=!0u16)
+            .map(encode)
+            .find(|&c| (c >> 1).count_ones() == 23)
+            .unwrap();
+        let mask = (0..12).fold((0, diff >> 1), |(m, c), _| {
+            let low = c & c.wrapping_neg();
+            (m | low, c ^ low)
+        });
+        let word = encode_extended(0b1010110000111101) ^ mask.0 << 1;
+
+        assert_eq!(
+            decode(word),
+            Ok((0b1010110000111101 ^ (diff >> 48) as u16, 11))
+        );
+        assert_eq!(
+            decode_extended(word),
+            Err(DecodeError::DetectedUncorrectable)
+        );
+
+        let word = encode_extended(0b1010110000111101);
+        assert_eq!(decode_extended(word), Ok((0b1010110000111101, 0)));
+        assert_eq!(decode_extended(word ^ 1), Ok((0b1010110000111101, 1)));
+        assert_eq!(
+            decode_extended(word ^ 0b111 << 40),
+            Ok((0b1010110000111101, 3))
+        );
+        assert_eq!(
+            decode_extended(word ^ 0b11_1111_1111 << 20 ^ 1),
+            Ok((0b1010110000111101, 11))
+        );
+        assert_eq!(
+            decode_extended(word ^ 0b111_1111_1111 << 20 ^ 1),
+            Err(DecodeError::DetectedUncorrectable)
+        );
 
         for i in 0..1u32 << 17 {
             assert_eq!(decode(encode(i as u16)).unwrap().0, i as u16);
@@ -0,0 +1,125 @@
+//! Common interface to the block codes.
+
+use crate::error::DecodeError;
+
+/// An (n, k, d) block code that maps k data bits or symbols to n-bit or n-symbol
+/// codewords with minimum distance d.
+pub trait BlockCode {
+    /// Type holding the data bits/symbols of a codeword.
+    type Data;
+    /// Type holding the bits/symbols of a full codeword.
+    type Codeword;
+
+    /// Number of bits/symbols in a codeword: n.
+    const N: usize;
+    /// Number of data bits/symbols in a codeword: k.
+    const K: usize;
+    /// Minimum Hamming distance between codewords: d.
+    const D: usize;
+
+    /// Encode the given data into a codeword.
+    fn encode(data: Self::Data) -> Self::Codeword;
+
+    /// Try to decode the given word to the nearest codeword.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the
+    /// decoded data and `err` is the number of corrected bits/symbols. Otherwise, return
+    /// an `Err` describing the unrecoverable error.
+    fn decode(word: Self::Codeword) -> Result<(Self::Data, usize), DecodeError>;
+}
+
+/// Declare a unit `Code` type implementing `BlockCode` for the (n, k, d) code with the
+/// given data and codeword types, forwarding to the given encode and decode functions.
+/// Any leading attributes, such as doc comments, are applied to the `Code` type.
+macro_rules! impl_block_code {
+    (
+        $(#[$attr:meta])*
+        Code: $n:expr, $k:expr, $d:expr, $data:ty, $codeword:ty, $encode:expr, $decode:expr
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug)]
+        pub struct Code;
+
+        impl $crate::coding::block::BlockCode for Code {
+            type Data = $data;
+            type Codeword = $codeword;
+
+            const N: usize = $n;
+            const K: usize = $k;
+            const D: usize = $d;
+
+            fn encode(data: $data) -> $codeword {
+                ($encode)(data)
+            }
+
+            fn decode(word: $codeword) -> Result<($data, usize), $crate::error::DecodeError> {
+                ($decode)(word)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bits::Hexbit;
+    use crate::coding::{bch, cyclic, golay, hamming, reed_solomon};
+
+    fn roundtrip<C>(data: C::Data)
+    where
+        C: BlockCode,
+        C::Data: Copy + PartialEq + std::fmt::Debug,
+    {
+        assert!(C::K < C::N);
+        assert!(C::D <= C::N - C::K + 1);
+        assert_eq!(C::decode(C::encode(data)), Ok((data, 0)));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip::<bch::Code>(0xbeef);
+        roundtrip::<cyclic::Code>(0xa5);
+        roundtrip::<golay::standard::Code>(0xabc);
+        roundtrip::<golay::extended::Code>(0x123);
+        roundtrip::<golay::shortened::Code>(0b101101);
+        roundtrip::<hamming::standard::Code>(0b10110011100);
+        roundtrip::<hamming::shortened::Code>(0b110011);
+
+        let mut data = [Hexbit::default(); 12];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(i as u8 * 5);
+        }
+        roundtrip::<reed_solomon::short::Code>(data);
+
+        let mut data = [Hexbit::default(); 16];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(i as u8 * 3);
+        }
+        roundtrip::<reed_solomon::medium::Code>(data);
+
+        let mut data = [Hexbit::default(); 20];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(63 - i as u8);
+        }
+        roundtrip::<reed_solomon::long::Code>(data);
+
+        let mut data = [Hexbit::default(); 30];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(i as u8 * 7 % 64);
+        }
+        roundtrip::<reed_solomon::phase2::facch::Code>(data);
+
+        let mut data = [Hexbit::default(); 26];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(i as u8 + 30);
+        }
+        roundtrip::<reed_solomon::phase2::sacch::Code>(data);
+        roundtrip::<reed_solomon::phase2::lcch::Code>(data);
+
+        let mut data = [Hexbit::default(); 16];
+        for (i, h) in data.iter_mut().enumerate() {
+            *h = Hexbit::new(i as u8 * 4);
+        }
+        roundtrip::<reed_solomon::phase2::ess::Code>(data);
+    }
+}
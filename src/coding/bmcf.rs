@@ -57,18 +57,35 @@
 //! long division, just a one-time polynomial multiplication and derivative evaluation
 //! to create Ω(x), then two polynomial evaluations and one codeword division for each
 //! error.
+//!
+//! # Erasures
+//!
+//! When the locations m<sub>1</sub>, ..., m<sub>f</sub> of some unreliable symbols are
+//! known in advance, the decoder can treat them as erasures and correct any combination
+//! of e errors and f erasures with 2e + f < d [2], [16]. The erasure locator Γ(x) = (1 +
+//! α<sup>m<sub>1</sub></sup>x) ··· (1 + α<sup>m<sub>f</sub></sup>x) is used to build the
+//! modified (Forney) syndromes T(x) = Γ(x)s(x) mod x<sup>2t</sup>, and the
+//! Berlekamp-Massey algorithm is run over the 2t - f coefficients T<sub>f</sub>, ...,
+//! T<sub>2t-1</sub> to find the error locator σ(x). The errata locator is then Λ(x) =
+//! Γ(x)σ(x), and the Chien Search and Forney steps proceed unchanged.
 
 use std;
 
 use collect_slice::CollectSlice;
 
-use crate::coding::galois::{GaloisField, P25Codeword, P25Field, Polynomial, PolynomialCoefs};
+use crate::coding::galois::{Codeword, GaloisField, Polynomial, PolynomialCoefs};
+use crate::error::DecodeError;
 
 /// Finds the error location polynomial Λ(x) from the syndrome polynomial s(x).
 ///
 /// This uses Hankerson et al's version of the Berlekamp-Massey algorithm, with the result
-/// being Λ(x) = p<sub>2t</sub>(x) = σ<sub>R</sub>(x).
+/// being Λ(x) = p<sub>2t</sub>(x) = σ<sub>R</sub>(x). When erasures are given, the result
+/// is the errata locator Λ(x) = Γ(x)σ(x).
 pub struct ErrorLocator<P: PolynomialCoefs> {
+    /// Erasure locator polynomial: Γ(x).
+    erasures: Polynomial<P>,
+    /// Number of iterations to perform: 2t - f.
+    steps: usize,
     /// Saved p polynomial: p<sub>zi-1</sub>.
     p_saved: Polynomial<P>,
     /// Previous iteration's p polynomial: p<sub>i-1</sub>.
@@ -86,17 +103,37 @@ pub struct ErrorLocator<P: PolynomialCoefs> {
 impl<P: PolynomialCoefs> ErrorLocator<P> {
     /// Construct a new `ErrorLocator` from the given syndrome polynomial s(x).
     pub fn new(syn: Polynomial<P>) -> ErrorLocator<P> {
+        Self::init(syn, Polynomial::unit_power(0), P::syndromes())
+    }
+
+    /// Construct a new `ErrorLocator` from the given syndrome polynomial s(x) and erasure
+    /// locator polynomial Γ(x). Panic if deg(Γ(x)) > 2t.
+    pub fn with_erasures(syn: Polynomial<P>, erasures: Polynomial<P>) -> ErrorLocator<P> {
+        let f = erasures.degree().expect("invalid erasure polynomial");
+        assert!(f <= P::syndromes());
+
+        // Compute T(x) = Γ(x)s(x) mod x^{2t} and keep the f+1, ..., 2t modified
+        // syndromes, which are generated by the error locator σ(x) alone.
+        let mods = (erasures * syn).truncate(P::syndromes() - 1);
+        let syn = Polynomial::new(mods.iter().skip(f).take(P::syndromes() - f).cloned());
+
+        Self::init(syn, erasures, P::syndromes() - f)
+    }
+
+    /// Set up the algorithm state for the given number of syndromes n.
+    fn init(syn: Polynomial<P>, erasures: Polynomial<P>, n: usize) -> ErrorLocator<P> {
         ErrorLocator {
+            erasures,
+            steps: n,
             // Compute 1 + s(x).
             q_saved: Polynomial::new(
-                std::iter::once(P25Codeword::for_power(0))
-                    .chain(syn.iter().take(P::syndromes()).cloned()),
+                std::iter::once(Codeword::for_power(0)).chain(syn.iter().take(n).cloned()),
             ),
             q_cur: syn,
-            // Compute x^{2t+1}.
-            p_saved: Polynomial::unit_power(P::syndromes() + 1),
-            // Compute x^{2t}.
-            p_cur: Polynomial::unit_power(P::syndromes()),
+            // Compute x^{n+1}.
+            p_saved: Polynomial::unit_power(n + 1),
+            // Compute x^n.
+            p_cur: Polynomial::unit_power(n),
             deg_saved: 0,
             deg_cur: 1,
         }
@@ -104,11 +141,11 @@ impl<P: PolynomialCoefs> ErrorLocator<P> {
 
     /// Construct the error locator polynomial Λ(x).
     pub fn build(mut self) -> Polynomial<P> {
-        for _ in 0..P::syndromes() {
+        for _ in 0..self.steps {
             self.step();
         }
 
-        self.p_cur
+        self.erasures * self.p_cur
     }
 
     /// Perform one iterative step of the algorithm, updating the state polynomials and
@@ -174,7 +211,7 @@ impl<P: PolynomialCoefs> PolynomialRoots<P> {
     pub fn new(loc: Polynomial<P>) -> Self {
         PolynomialRoots {
             loc,
-            pow: 0..P25Field::size(),
+            pow: 0..P::Field::size(),
         }
     }
 
@@ -182,29 +219,24 @@ impl<P: PolynomialCoefs> PolynomialRoots<P> {
     /// power.
     fn update_terms(&mut self) {
         for (pow, term) in self.loc.iter_mut().enumerate() {
-            *term = *term * P25Codeword::for_power(pow);
+            *term = *term * Codeword::for_power(pow);
         }
     }
 
     /// Compute Λ(α<sup>i</sup>), where i is the current power.
-    fn eval(&self) -> P25Codeword {
-        self.loc
-            .iter()
-            .fold(P25Codeword::default(), |sum, &x| sum + x)
+    fn eval(&self) -> Codeword<P::Field> {
+        self.loc.iter().fold(Codeword::default(), |sum, &x| sum + x)
     }
 }
 
 /// Iterate over all roots α<sup>i</sup> of Λ(x).
 impl<P: PolynomialCoefs> Iterator for PolynomialRoots<P> {
-    type Item = P25Codeword;
+    type Item = Codeword<P::Field>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             // Current codeword power: i in α^i.
-            let pow = match self.pow.next() {
-                Some(pow) => pow,
-                None => return None,
-            };
+            let pow = self.pow.next()?;
 
             // Compute Λ(α^i).
             let eval = self.eval();
@@ -213,7 +245,7 @@ impl<P: PolynomialCoefs> Iterator for PolynomialRoots<P> {
 
             // Yield α^i if Λ(α^i) = 0.
             if eval.zero() {
-                return Some(P25Codeword::for_power(pow));
+                return Some(Codeword::for_power(pow));
             }
         }
     }
@@ -233,7 +265,7 @@ pub struct ErrorDescriptions<P: PolynomialCoefs> {
 
 impl<P: PolynomialCoefs> ErrorDescriptions<P> {
     /// Create a new `ErrorDescriptions` from the given syndrome polynomial s(x) and error
-    /// locator polynomial Λ(x).
+    /// locator polynomial Λ(x), which may also be an errata locator.
     pub fn new(syn: Polynomial<P>, loc: Polynomial<P>) -> Self {
         ErrorDescriptions {
             // Compute Λ'(x).
@@ -245,7 +277,7 @@ impl<P: PolynomialCoefs> ErrorDescriptions<P> {
 
     /// Compute the error location and pattern for the given root
     /// a<sub>i</sub><sup>-1</sup> of Λ(x).
-    pub fn for_root(&self, root: P25Codeword) -> (usize, P25Codeword) {
+    pub fn for_root(&self, root: Codeword<P::Field>) -> (usize, Codeword<P::Field>) {
         (
             // If Λ(α^i) = 0, then the error location is m ≡ -i (modulo the field.)
             root.invert().power().unwrap(),
@@ -271,15 +303,54 @@ pub struct Errors<P: PolynomialCoefs> {
 impl<P: PolynomialCoefs> Errors<P> {
     /// Create a new `Errors` decoder from the given syndrome polynomial s(x).
     ///
-    /// If decoding was sucessful, return `Some((nerr, errs))`, where `nerr` is the number
-    /// of detected errors and `errs` is the error iterator. Otherwise, return `None` to
-    /// indicate an unrecoverable error.
-    pub fn new(syn: Polynomial<P>) -> Option<(usize, Self)> {
-        // Compute error locator polynomial Λ(x).
-        let loc = ErrorLocator::new(syn).build();
-        // If e = deg(Λ), then e ≤ t and e represents the number of detected errors.
+    /// If decoding was sucessful, return `Ok((nerr, errs))`, where `nerr` is the number
+    /// of detected errors and `errs` is the error iterator. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn new(syn: Polynomial<P>) -> Result<(usize, Self), DecodeError> {
+        Self::with_erasures(syn, std::iter::empty())
+    }
+
+    /// Create a new `Errors` decoder from the given syndrome polynomial s(x) and the
+    /// distinct locations m<sub>i</sub> of erased symbols.
+    ///
+    /// If decoding was sucessful, return `Ok((nerr, errs))`, where `nerr` is the number
+    /// of errors and erasures and `errs` is the errata iterator, which may yield a zero
+    /// pattern for an erased symbol that was received correctly. Otherwise, return an
+    /// `Err` describing the unrecoverable error, including when more than 2t erasures are
+    /// given.
+    pub fn with_erasures<E>(syn: Polynomial<P>, erasures: E) -> Result<(usize, Self), DecodeError>
+    where
+        E: Iterator<Item = usize>,
+    {
+        // Compute erasure locator polynomial Γ(x).
+        let mut gamma = Polynomial::<P>::unit_power(0);
+        let mut f = 0;
+
+        for loc in erasures {
+            if f == P::syndromes() {
+                return Err(DecodeError::TooManyErasures);
+            }
+
+            f += 1;
+            gamma = gamma
+                * Polynomial::new(
+                    [Codeword::for_power(0), Codeword::for_power(loc)]
+                        .iter()
+                        .cloned(),
+                );
+        }
+
+        // Compute errata locator polynomial Λ(x).
+        let loc = ErrorLocator::with_erasures(syn, gamma).build();
+        // The degree e = deg(Λ) represents the number of detected errors and erasures.
         let errors = loc.degree().expect("invalid error polynomial");
 
+        // The e - f errors and f erasures must be within the correction capability of the
+        // code: 2(e - f) + f ≤ 2t.
+        if errors < f || 2 * (errors - f) + f > P::syndromes() {
+            return Err(DecodeError::Uncorrectable);
+        }
+
         // Find the roots a_i of Λ(x). These are buffered before processing them because
         // if the number of found roots ends up unequal to deg(Λ(x)), all the roots are
         // invalid, and processing them before checking this can cause behavior like
@@ -290,10 +361,10 @@ impl<P: PolynomialCoefs> Errors<P> {
         // If the number of computed roots is different than deg(Λ), then the roots are
         // invalid and the codeword is unrecoverable [1, p3], [2, p48], [3, p22].
         if nroots != errors {
-            return None;
+            return Err(DecodeError::RootCountMismatch);
         }
 
-        Some((
+        Ok((
             errors,
             Errors {
                 roots,
@@ -306,7 +377,7 @@ impl<P: PolynomialCoefs> Errors<P> {
 
 /// Iterate over detected errors, yielding the location and pattern of each error.
 impl<P: PolynomialCoefs> Iterator for Errors<P> {
-    type Item = (usize, P25Codeword);
+    type Item = (usize, Codeword<P::Field>);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.pos.next().map(|i| self.descs.for_root(self.roots[i]))
@@ -316,13 +387,19 @@ impl<P: PolynomialCoefs> Iterator for Errors<P> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::coding::galois::{P25Codeword, Polynomial, PolynomialCoefs};
+    use crate::coding::galois::{P25Codeword, Polynomial};
     use collect_slice::CollectSlice;
     use std;
 
     impl_polynomial_coefs!(TestCoefs, 9);
     type TestPolynomial = Polynomial<TestCoefs>;
 
+    define_field!(Gf256, 0x11d);
+    type Gf256Codeword = Codeword<Gf256>;
+
+    impl_polynomial_coefs!(Gf256Coefs: Gf256, 9, 32);
+    type Gf256Polynomial = Polynomial<Gf256Coefs>;
+
     #[test]
     fn test_roots() {
         // p(x) = (1+α^42x)(1+α^13x)(1+α^57x)
@@ -353,4 +430,54 @@ mod test {
         let mut r = PolynomialRoots::new(p);
         assert!(r.next().is_none());
     }
+
+    #[test]
+    fn test_other_field() {
+        // Build the (32, 24, 9) Reed-Solomon code over GF(2^8) with generator g(x) = (x +
+        // α)(x + α^2) ··· (x + α^8), and encode non-systematically as c(x) = m(x)g(x).
+        let gen = (1..=8).fold(Gf256Polynomial::unit_power(0), |g, p| {
+            g * Gf256Polynomial::new(
+                [Gf256Codeword::for_power(p), Gf256Codeword::for_power(0)]
+                    .iter()
+                    .cloned(),
+            )
+        });
+
+        let msg = Gf256Polynomial::new((0..24).map(|i| Gf256Codeword::new(i * 10 + 3)));
+        let word = msg * gen;
+
+        let mut recv = word;
+        recv[0] = recv[0] + Gf256Codeword::new(0xff);
+        recv[13] = recv[13] + Gf256Codeword::new(0x01);
+        recv[20] = recv[20] + Gf256Codeword::new(0x5a);
+        recv[31] = recv[31] + Gf256Codeword::new(0x80);
+
+        let syn = Gf256Polynomial::new((1..=8).map(|p| recv.eval(Gf256Codeword::for_power(p))));
+        let (nerr, errs) = Errors::new(syn).unwrap();
+        assert_eq!(nerr, 4);
+
+        for (loc, pat) in errs {
+            recv[loc] = recv[loc] + pat;
+        }
+
+        assert_eq!(&recv[..], &word[..]);
+
+        // Recover 2 errors and 4 erasures, one of which holds the correct symbol.
+        let mut recv = word;
+        recv[2] = recv[2] + Gf256Codeword::new(0x33);
+        recv[3] = recv[3] + Gf256Codeword::new(0x44);
+        recv[4] = recv[4] + Gf256Codeword::new(0x55);
+        recv[17] = recv[17] + Gf256Codeword::new(0x66);
+        recv[30] = recv[30] + Gf256Codeword::new(0x77);
+
+        let syn = Gf256Polynomial::new((1..=8).map(|p| recv.eval(Gf256Codeword::for_power(p))));
+        let (nerr, errs) = Errors::with_erasures(syn, [2, 3, 4, 5].iter().cloned()).unwrap();
+        assert_eq!(nerr, 6);
+
+        for (loc, pat) in errs {
+            recv[loc] = recv[loc] + pat;
+        }
+
+        assert_eq!(&recv[..], &word[..]);
+    }
 }
@@ -0,0 +1,51 @@
+//! Details of the symbols corrected while decoding a received word.
+
+/// Correction made to a single symbol of a received word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Correction {
+    /// Index of the symbol in the received word, where index 0 is the first symbol
+    /// transmitted: the MSB of a binary word or the first entry of a symbol buffer.
+    pub index: usize,
+    /// Error magnitude that was added to the received symbol to correct it. This is
+    /// always 1 for binary codes.
+    pub pattern: u16,
+}
+
+/// Collect the corrections for the given error pattern over an n-bit binary word, where
+/// each set bit marks a corrected bit.
+pub fn bit_corrections(pattern: u64, n: usize) -> Vec<Correction> {
+    assert!(n == 64 || pattern >> n == 0);
+
+    (0..n)
+        .filter(|&index| pattern >> (n - index - 1) & 1 == 1)
+        .map(|index| Correction { index, pattern: 1 })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bit_corrections() {
+        assert_eq!(bit_corrections(0, 16), vec![]);
+        assert_eq!(
+            bit_corrections(0b1000_0000_0000_0101, 16),
+            vec![
+                Correction {
+                    index: 0,
+                    pattern: 1
+                },
+                Correction {
+                    index: 13,
+                    pattern: 1
+                },
+                Correction {
+                    index: 15,
+                    pattern: 1
+                },
+            ]
+        );
+        assert_eq!(bit_corrections(1 << 63, 64).len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+//! Cyclic redundancy checks used by P25, along with single-bit error correction from the
+//! CRC syndrome.
+//!
+//! Each CRC is computed MSB-first without reflection, with the register started at the
+//! given initial value and the result XORed with the given output mask.
+
+use crate::bits::Dibit;
+use crate::error::DecodeError;
+
+/// The 16-bit CRC-CCITT protecting TSBKs and PDU headers.
+pub const CRC_CCITT: Crc = Crc::new(16, 0x1021, 0, 0xffff);
+
+/// The 9-bit CRC protecting the serial number and data of each confirmed PDU data block.
+pub const CRC_9: Crc = Crc::new(9, 0x059, 0, 0x1ff);
+
+/// The 32-bit CRC protecting the payload of a PDU.
+pub const CRC_32: Crc = Crc::new(32, 0x04c1_1db7, 0, 0xffff_ffff);
+
+/// Parameters of a CRC of up to 32 bits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crc {
+    /// Number of bits in the CRC.
+    width: u32,
+    /// Generator polynomial, excluding the implicit leading term.
+    poly: u32,
+    /// Initial register value.
+    init: u32,
+    /// Mask XORed onto the final register value.
+    xor: u32,
+}
+
+impl Crc {
+    /// Construct a new `Crc` with the given width, generator polynomial (excluding the
+    /// leading term), initial register value, and output mask.
+    pub const fn new(width: u32, poly: u32, init: u32, xor: u32) -> Crc {
+        assert!(width > 0 && width <= 32);
+
+        Crc {
+            width,
+            poly,
+            init,
+            xor,
+        }
+    }
+
+    /// Number of bits in the CRC.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Start a new running CRC calculation.
+    pub fn digest(&self) -> Digest {
+        Digest {
+            crc: *self,
+            reg: self.init,
+        }
+    }
+
+    /// Calculate the CRC over the given bytes.
+    pub fn checksum(&self, bytes: &[u8]) -> u32 {
+        let mut digest = self.digest();
+        digest.feed_bytes(bytes);
+        digest.finish()
+    }
+
+    /// Calculate the CRC over the given bits.
+    pub fn checksum_bits<T: IntoIterator<Item = bool>>(&self, bits: T) -> u32 {
+        let mut digest = self.digest();
+        bits.into_iter().for_each(|bit| digest.feed_bit(bit));
+        digest.finish()
+    }
+
+    /// Calculate the CRC over the given dibits.
+    pub fn checksum_dibits<T: IntoIterator<Item = Dibit>>(&self, dibits: T) -> u32 {
+        let mut digest = self.digest();
+        dibits
+            .into_iter()
+            .for_each(|dibit| digest.feed_dibit(dibit));
+        digest.finish()
+    }
+
+    /// Try to correct a single bit error in the given message bits or in the given
+    /// received CRC, using the difference between the received and calculated CRCs as
+    /// the error syndrome. This is only reliable for blocks short enough that every
+    /// single-bit error has a distinct syndrome.
+    ///
+    /// If the message and CRC agree, or a single bit error was found, return `Ok(err)`,
+    /// where `err` is the number of corrected bits, and any error in the message is
+    /// corrected in place. Otherwise, return `Err(DecodeError::Uncorrectable)` if no
+    /// single bit error matches the syndrome or `Err(DecodeError::Ambiguous)` if more than
+    /// one does.
+    pub fn correct_bit(&self, bits: &mut [bool], crc: u32) -> Result<usize, DecodeError> {
+        let syndrome = self.checksum_bits(bits.iter().cloned()) ^ crc;
+
+        if syndrome == 0 {
+            return Ok(0);
+        }
+
+        // An error in the CRC field flips the same bit of the syndrome.
+        let mut found = if syndrome.count_ones() == 1 {
+            vec![None]
+        } else {
+            vec![]
+        };
+
+        // Syndrome of an error in the last message bit, which is then shifted through the
+        // register once for each following bit.
+        let mut pattern = self.poly & self.mask();
+
+        for idx in (0..bits.len()).rev() {
+            if pattern == syndrome {
+                found.push(Some(idx));
+            }
+
+            pattern = self.step(pattern, false);
+        }
+
+        match found[..] {
+            [] => Err(DecodeError::Uncorrectable),
+            [loc] => {
+                if let Some(idx) = loc {
+                    bits[idx] = !bits[idx];
+                }
+
+                Ok(1)
+            }
+            _ => Err(DecodeError::Ambiguous),
+        }
+    }
+
+    /// Shift the given bit into the given register.
+    fn step(&self, reg: u32, bit: bool) -> u32 {
+        let msb = reg >> (self.width - 1) & 1 == 1;
+        let reg = reg << 1 & self.mask();
+
+        if msb ^ bit {
+            reg ^ self.poly
+        } else {
+            reg
+        }
+    }
+
+    /// Mask covering the bits of the register.
+    fn mask(&self) -> u32 {
+        u32::MAX >> (32 - self.width)
+    }
+}
+
+/// Running CRC calculation.
+#[derive(Copy, Clone, Debug)]
+pub struct Digest {
+    /// CRC parameters.
+    crc: Crc,
+    /// Current register value.
+    reg: u32,
+}
+
+impl Digest {
+    /// Feed in the given bit.
+    pub fn feed_bit(&mut self, bit: bool) {
+        self.reg = self.crc.step(self.reg, bit);
+    }
+
+    /// Feed in the given dibit, MSB first.
+    pub fn feed_dibit(&mut self, dibit: Dibit) {
+        self.feed_bit(dibit.hi() == 1);
+        self.feed_bit(dibit.lo() == 1);
+    }
+
+    /// Feed in the given byte, MSB first.
+    pub fn feed_byte(&mut self, byte: u8) {
+        for i in (0..8).rev() {
+            self.feed_bit(byte >> i & 1 == 1);
+        }
+    }
+
+    /// Feed in each of the given bytes.
+    pub fn feed_bytes(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|&byte| self.feed_byte(byte));
+    }
+
+    /// Finish the calculation and return the CRC.
+    pub fn finish(self) -> u32 {
+        (self.reg ^ self.crc.xor) & self.crc.mask()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bits::Dibits;
+
+    fn to_bits(bytes: &[u8]) -> Vec<bool> {
+        bytes
+            .iter()
+            .flat_map(|&b| (0..8).rev().map(move |i| b >> i & 1 == 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(CRC_CCITT.checksum(b"123456789"), 0xce3c);
+        assert_eq!(CRC_CCITT.checksum(&[]), 0xffff);
+        assert_eq!(CRC_32.checksum(b"123456789"), 0x765e_7680);
+        assert_eq!(CRC_9.checksum(&[]), 0x1ff);
+        assert_eq!(CRC_9.checksum(b"123456789"), 0x0e3);
+    }
+
+    #[test]
+    fn test_iters() {
+        let bytes = b"\x12\x34\x56\x78\x9a\xbc";
+
+        for crc in [CRC_CCITT, CRC_9, CRC_32].iter() {
+            let sum = crc.checksum(bytes);
+
+            assert_eq!(crc.checksum_bits(to_bits(bytes)), sum);
+            assert_eq!(crc.checksum_dibits(Dibits::new(bytes.iter().cloned())), sum);
+
+            let mut digest = crc.digest();
+            digest.feed_bytes(&bytes[..2]);
+            digest.feed_bit(false);
+            digest.feed_bit(true);
+            digest.feed_dibit(Dibit::new(0b01));
+            digest.feed_dibit(Dibit::new(0b01));
+            digest.feed_dibit(Dibit::new(0b10));
+            digest.feed_bytes(&bytes[3..]);
+            assert_eq!(digest.finish(), sum);
+        }
+
+        // Bit-level messages needn't fill whole bytes.
+        let bits = [true, false, true, true, false, false, true];
+        assert_eq!(CRC_9.checksum_bits(bits.iter().cloned()) >> 9, 0);
+    }
+
+    #[test]
+    fn test_correct_bit() {
+        let bytes = b"\xde\xad\xbe\xef\x01\x23\x45\x67";
+        let orig = to_bits(bytes);
+
+        for crc in [CRC_CCITT, CRC_9, CRC_32].iter() {
+            let sum = crc.checksum(bytes);
+
+            let mut bits = orig.clone();
+            assert_eq!(crc.correct_bit(&mut bits, sum), Ok(0));
+            assert_eq!(bits, orig);
+
+            for i in 0..bits.len() {
+                bits[i] = !bits[i];
+                assert_eq!(crc.correct_bit(&mut bits, sum), Ok(1));
+                assert_eq!(bits, orig);
+            }
+
+            for i in 0..crc.width() {
+                assert_eq!(crc.correct_bit(&mut bits, sum ^ 1 << i), Ok(1));
+                assert_eq!(bits, orig);
+            }
+        }
+
+        // Two errors can't be corrected.
+        let mut bits = orig.clone();
+        bits[0] = !bits[0];
+        bits[1] = !bits[1];
+        assert_eq!(
+            CRC_32.correct_bit(&mut bits, CRC_32.checksum(bytes)),
+            Err(DecodeError::Uncorrectable)
+        );
+    }
+}
@@ -5,6 +5,9 @@
 
 use cai_cyclic;
 
+use crate::coding::correction::{bit_corrections, Correction};
+use crate::error::DecodeError;
+
 /// Encode the given 8 data bits into a 16-bit codeword.
 pub fn encode(data: u8) -> u16 {
     cai_cyclic::encode(data as u16) as u16
@@ -13,20 +16,38 @@ pub fn encode(data: u8) -> u16 {
 /// Try to decode the given 16-bit word to the nearest codeword, correcting up to 2
 /// errors.
 ///
-/// If decoding was successful, return `Some((data, err))`, where `data` is the 8 data
-/// bits and `err` is the number of corrected bits. Otherwise, return `None` to indicate
-/// an unrecoverable error.
-pub fn decode(word: u16) -> Option<(u8, usize)> {
-    cai_cyclic::decode(word as u32).and_then(|(word, err)| {
-        if word >> 8 == 0 {
-            Some((word as u8, err))
-        } else {
-            None
+/// If decoding was successful, return `Ok((data, err))`, where `data` is the 8 data
+/// bits and `err` is the number of corrected bits. Otherwise, return an `Err` describing
+/// the unrecoverable error.
+pub fn decode(word: u16) -> Result<(u8, usize), DecodeError> {
+    decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+}
+
+/// Try to decode the given 16-bit word to the nearest codeword, correcting up to 2
+/// errors.
+///
+/// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 8 data
+/// bits and `fixes` describes each corrected bit. Otherwise, return an `Err` describing
+/// the unrecoverable error.
+pub fn decode_detailed(word: u16) -> Result<(u8, Vec<Correction>), DecodeError> {
+    match cai_cyclic::decode(word as u32) {
+        Some((data, _)) if data >> 8 == 0 => {
+            let pattern = encode(data as u8) ^ word;
+            Ok((data as u8, bit_corrections(pattern.into(), 16)))
         }
-    })
+        Some(_) => Err(DecodeError::ShortenedOverflow),
+        None => Err(DecodeError::Uncorrectable),
+    }
+}
+
+impl_block_code! {
+    /// The (16, 8, 5) cyclic code, shortened from the (17, 8, 5) code to protect each
+    /// byte of LDU low-speed data.
+    Code: 16, 8, 5, u8, u16, encode, decode
 }
 
 #[cfg(test)]
+#[allow(clippy::identity_op, clippy::unnecessary_cast)]
 mod test {
     use super::*;
 
@@ -36,33 +57,40 @@ mod test {
         let e = encode(w);
         assert_eq!(e, 0b1010_1011_0111_1011);
 
-        assert_eq!(Some((w, 0)), decode(e ^ 0b0000000000000000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b1000000000000001));
-        assert_eq!(Some((w, 1)), decode(e ^ 0b0001000000000000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0011000000000000));
+        assert_eq!(Ok((w, 0)), decode(e ^ 0b0000000000000000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b1000000000000001));
+        assert_eq!(Ok((w, 1)), decode(e ^ 0b0001000000000000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0011000000000000));
+
+        assert_eq!(Ok((w, 1)), decode(e ^ 0b1000000000000000));
+        assert_eq!(Ok((w, 1)), decode(e ^ 0b0100000000000000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0010000000000001));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0001000000000010));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000100000000100));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000010000001000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000001000010000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000000100100000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000000011000000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000000001010000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000000010001000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000000100000100));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000001000000010));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0000010000000001));
+        assert_eq!(Ok((w, 1)), decode(e ^ 0b0000100000000000));
+        assert_eq!(Ok((w, 1)), decode(e ^ 0b0001000000000000));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0010000000000001));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b0100000000000100));
+        assert_eq!(Ok((w, 2)), decode(e ^ 0b1000000000001000));
 
-        assert_eq!(Some((w, 1)), decode(e ^ 0b1000000000000000));
-        assert_eq!(Some((w, 1)), decode(e ^ 0b0100000000000000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0010000000000001));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0001000000000010));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000100000000100));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000010000001000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000001000010000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000000100100000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000000011000000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000000001010000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000000010001000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000000100000100));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000001000000010));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0000010000000001));
-        assert_eq!(Some((w, 1)), decode(e ^ 0b0000100000000000));
-        assert_eq!(Some((w, 1)), decode(e ^ 0b0001000000000000));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0010000000000001));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b0100000000000100));
-        assert_eq!(Some((w, 2)), decode(e ^ 0b1000000000001000));
+        let (data, fixes) = decode_detailed(e ^ 0b0100000000000100).unwrap();
+        assert_eq!(data, w);
+        assert_eq!(
+            fixes.iter().map(|c| c.index).collect::<Vec<_>>(),
+            vec![1, 13]
+        );
 
         for w in 0..=(!0u8) {
-            assert_eq!(decode(encode(w as u8)), Some((w, 0)));
+            assert_eq!(decode(encode(w as u8)), Ok((w, 0)));
         }
     }
 }
@@ -7,45 +7,117 @@ use collect_slice::CollectSlice;
 /// Codeword in the P25 Galois field.
 pub type P25Codeword = Codeword<P25Field>;
 
-/// GF(2<sup>6</sup>) field characterized by α<sup>6</sup>+α+1, as described in the P25
-/// specification.
-#[derive(Copy, Clone, Debug)]
-pub struct P25Field;
+/// Define a GF(2<sup>r</sup>) Galois field type, 2 ≤ r ≤ 16, characterized by the given
+/// primitive polynomial h(x), where the bit at index i holds the coefficient of
+/// x<sup>i</sup>.
+///
+/// The power and codeword tables are generated at compile time, and compilation fails if
+/// h(x) isn't primitive.
+///
+/// ```
+/// # #[macro_use] extern crate code_rs;
+/// # use code_rs::coding::galois::{Codeword, GaloisField};
+/// define_field!(Gf256, 0x11d);
+///
+/// # fn main() {
+/// assert_eq!(Gf256::size(), 255);
+/// assert_eq!(Codeword::<Gf256>::for_power(8).bits(), 0x1d);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_field {
+    ($(#[$attr:meta])* $name:ident, $poly:expr) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug)]
+        pub struct $name;
+
+        impl $crate::coding::galois::GaloisField for $name {
+            fn size() -> usize {
+                $crate::coding::galois::field_size($poly)
+            }
 
-impl GaloisField for P25Field {
-    fn size() -> usize {
-        63
-    }
-    fn valid_codeword(bits: u8) -> bool {
-        bits >> 6 == 0
-    }
+            fn valid_codeword(bits: u16) -> bool {
+                bits as usize <= Self::size()
+            }
 
-    fn codeword(pow: usize) -> u8 {
-        // Each codeword α^i represents the polynomial x^i mod h(x), where P25 uses h(x) =
-        // x^6 + x + 1.
-        const CODEWORDS: [u8; 63] = [
-            0b000001, 0b000010, 0b000100, 0b001000, 0b010000, 0b100000, 0b000011, 0b000110,
-            0b001100, 0b011000, 0b110000, 0b100011, 0b000101, 0b001010, 0b010100, 0b101000,
-            0b010011, 0b100110, 0b001111, 0b011110, 0b111100, 0b111011, 0b110101, 0b101001,
-            0b010001, 0b100010, 0b000111, 0b001110, 0b011100, 0b111000, 0b110011, 0b100101,
-            0b001001, 0b010010, 0b100100, 0b001011, 0b010110, 0b101100, 0b011011, 0b110110,
-            0b101111, 0b011101, 0b111010, 0b110111, 0b101101, 0b011001, 0b110010, 0b100111,
-            0b001101, 0b011010, 0b110100, 0b101011, 0b010101, 0b101010, 0b010111, 0b101110,
-            0b011111, 0b111110, 0b111111, 0b111101, 0b111001, 0b110001, 0b100001,
-        ];
+            fn codeword(pow: usize) -> u16 {
+                const SIZE: usize = $crate::coding::galois::field_size($poly);
+                static CODEWORDS: [u16; SIZE] = $crate::coding::galois::codewords($poly);
 
-        CODEWORDS[pow]
+                CODEWORDS[pow]
+            }
+
+            fn power(codeword: usize) -> usize {
+                const SIZE: usize = $crate::coding::galois::field_size($poly);
+                static POWERS: [u16; SIZE] = $crate::coding::galois::powers($poly);
+
+                POWERS[codeword] as usize
+            }
+        }
+    };
+}
+
+define_field!(
+    /// GF(2<sup>6</sup>) field characterized by α<sup>6</sup>+α+1, as described in the P25
+    /// specification.
+    P25Field,
+    0b1000011
+);
+
+/// Compute the number of nonzero codewords, 2<sup>r</sup> - 1, in the field characterized
+/// by the given degree-r polynomial h(x). Panic if r isn't in the supported range [2,
+/// 16].
+pub const fn field_size(poly: u32) -> usize {
+    let r = 31 - poly.leading_zeros();
+    assert!(r >= 2 && r <= 16, "unsupported field degree");
+
+    (1 << r) - 1
+}
+
+/// Generate the table mapping each power i to the codeword α<sup>i</sup> in the field
+/// characterized by the given primitive polynomial h(x). Panic if h(x) isn't primitive or
+/// the table size doesn't match the field.
+///
+/// Each codeword α<sup>i</sup> represents the polynomial x<sup>i</sup> mod h(x).
+pub const fn codewords<const N: usize>(poly: u32) -> [u16; N] {
+    assert!(N == field_size(poly));
+
+    let mut table = [0; N];
+    let mut word = 1;
+    let mut pow = 0;
+
+    while pow < N {
+        // A primitive element only returns to 1 after visiting every nonzero codeword.
+        assert!(pow == 0 || word != 1, "polynomial isn't primitive");
+        table[pow] = word as u16;
+
+        // Multiply by x and reduce modulo h(x).
+        word <<= 1;
+
+        if word > N as u32 {
+            word ^= poly;
+        }
+
+        pow += 1;
     }
 
-    fn power(codeword: usize) -> usize {
-        const POWERS: [usize; 63] = [
-            0, 1, 6, 2, 12, 7, 26, 3, 32, 13, 35, 8, 48, 27, 18, 4, 24, 33, 16, 14, 52, 36, 54, 9,
-            45, 49, 38, 28, 41, 19, 56, 5, 62, 25, 11, 34, 31, 17, 47, 15, 23, 53, 51, 37, 44, 55,
-            40, 10, 61, 46, 30, 50, 22, 39, 43, 29, 60, 42, 21, 20, 59, 57, 58,
-        ];
+    table
+}
 
-        POWERS[codeword]
+/// Generate the table mapping each codeword α<sup>i</sup> to its power i in the field
+/// characterized by the given primitive polynomial h(x). The table is indexed by the
+/// codeword's bit pattern minus one.
+pub const fn powers<const N: usize>(poly: u32) -> [u16; N] {
+    let words: [u16; N] = codewords(poly);
+    let mut table = [0; N];
+    let mut pow = 0;
+
+    while pow < N {
+        table[words[pow] as usize - 1] = pow as u16;
+        pow += 1;
     }
+
+    table
 }
 
 /// A GF(2<sup>r</sup>) Galois field.
@@ -53,35 +125,42 @@ pub trait GaloisField {
     /// Number of unique codewords in the field: 2<sup>r</sup> - 1.
     fn size() -> usize;
     /// Check if the given bit pattern is a valid codeword in the field.
-    fn valid_codeword(bits: u8) -> bool;
+    fn valid_codeword(bits: u16) -> bool;
     /// Map the given power i to codeword α<sup>i</sup>.
-    fn codeword(pow: usize) -> u8;
+    fn codeword(pow: usize) -> u16;
     /// Map the given codeword a<sup>i</sup> to its power i.
     fn power(codeword: usize) -> usize;
 
     /// Map the given power i to codeword α<sup>m</sup> ≡ α<sup>i</sup> (modulo the size
     /// of the field.)
-    fn codeword_modded(pow: usize) -> u8 {
+    fn codeword_modded(pow: usize) -> u16 {
         Self::codeword(pow % Self::size())
     }
 }
 
 /// Codeword in a Galois field.
-#[derive(Copy, Clone)]
 pub struct Codeword<F: GaloisField> {
     field: std::marker::PhantomData<F>,
-    bits: u8,
+    bits: u16,
+}
+
+impl<F: GaloisField> Copy for Codeword<F> {}
+
+impl<F: GaloisField> Clone for Codeword<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
 impl<F: GaloisField> Codeword<F> {
     /// Construct a new `Codeword` α<sup>i</sup> from the given bit pattern. Panic if the
     /// pattern is invalid in the field.
-    pub fn new(bits: u8) -> Codeword<F> {
+    pub fn new(bits: u16) -> Codeword<F> {
         assert!(F::valid_codeword(bits));
 
         Codeword {
             field: std::marker::PhantomData,
-            bits: bits,
+            bits,
         }
     }
 
@@ -92,7 +171,7 @@ impl<F: GaloisField> Codeword<F> {
     }
 
     /// Retrieve the bit pattern of the codeword.
-    pub fn bits(&self) -> u8 {
+    pub fn bits(&self) -> u16 {
         self.bits
     }
 
@@ -142,6 +221,7 @@ impl<F: GaloisField> Default for Codeword<F> {
 impl<F: GaloisField> std::ops::Add for Codeword<F> {
     type Output = Codeword<F>;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn add(self, rhs: Codeword<F>) -> Self::Output {
         Codeword::new(self.bits ^ rhs.bits)
     }
@@ -151,6 +231,7 @@ impl<F: GaloisField> std::ops::Add for Codeword<F> {
 impl<F: GaloisField> std::ops::Sub for Codeword<F> {
     type Output = Codeword<F>;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Codeword<F>) -> Self::Output {
         self + rhs
     }
@@ -160,6 +241,7 @@ impl<F: GaloisField> std::ops::Sub for Codeword<F> {
 impl<F: GaloisField> std::ops::Mul for Codeword<F> {
     type Output = Codeword<F>;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn mul(self, rhs: Codeword<F>) -> Self::Output {
         match (self.power(), rhs.power()) {
             (Some(p), Some(q)) => Codeword::for_power(p + q),
@@ -192,8 +274,8 @@ impl<F: GaloisField> std::cmp::PartialEq for Codeword<F> {
 impl<F: GaloisField> std::cmp::Eq for Codeword<F> {}
 
 /// Check equality of the codeword's bit pattern with raw bits.
-impl<F: GaloisField> std::cmp::PartialEq<u8> for Codeword<F> {
-    fn eq(&self, other: &u8) -> bool {
+impl<F: GaloisField> std::cmp::PartialEq<u16> for Codeword<F> {
+    fn eq(&self, other: &u16) -> bool {
         self.bits == *other
     }
 }
@@ -209,8 +291,15 @@ impl<F: GaloisField> std::fmt::Debug for Codeword<F> {
 
 /// Coefficient storage for a bounded-degree Galois polynomial of a particular code.
 pub trait PolynomialCoefs:
-    Default + Copy + Clone + std::ops::Deref<Target = [P25Codeword]> + std::ops::DerefMut
+    Default
+    + Copy
+    + Clone
+    + std::ops::Deref<Target = [Codeword<<Self as PolynomialCoefs>::Field>]>
+    + std::ops::DerefMut
 {
+    /// Galois field of the coefficients.
+    type Field: GaloisField;
+
     /// The minimum Hamming distance, d, in (n,k,d).
     fn distance() -> usize;
 
@@ -238,16 +327,27 @@ pub trait PolynomialCoefs:
 
 /// Create a coefficient storage buffer for the code of given distance. In the first form,
 /// the polynomial is large enough to store the Berlekamp-Massey decoding polynomials. In
-/// the second form, the polynomial has the given size.
+/// the second form, the polynomial has the given size. Coefficients are taken from the
+/// P25 field unless another field is given as `Name: Field`, and the buffer type may be
+/// prefixed with a visibility such as `pub`.
+#[macro_export]
 macro_rules! impl_polynomial_coefs {
-    ($name:ident, $dist:expr) => {
-        impl_polynomial_coefs!($name, $dist, $dist + 1);
+    ($vis:vis $name:ident, $dist:expr) => {
+        impl_polynomial_coefs!($vis $name: $crate::coding::galois::P25Field, $dist);
     };
-    ($name:ident, $dist:expr, $len:expr) => {
+    ($vis:vis $name:ident, $dist:expr, $len:expr) => {
+        impl_polynomial_coefs!($vis $name: $crate::coding::galois::P25Field, $dist, $len);
+    };
+    ($vis:vis $name:ident: $field:ty, $dist:expr) => {
+        impl_polynomial_coefs!($vis $name: $field, $dist, $dist + 1);
+    };
+    ($vis:vis $name:ident: $field:ty, $dist:expr, $len:expr) => {
         #[derive(Copy)]
-        struct $name([P25Codeword; $len]);
+        $vis struct $name([$crate::coding::galois::Codeword<$field>; $len]);
+
+        impl $crate::coding::galois::PolynomialCoefs for $name {
+            type Field = $field;
 
-        impl PolynomialCoefs for $name {
             fn distance() -> usize {
                 $dist
             }
@@ -255,20 +355,18 @@ macro_rules! impl_polynomial_coefs {
 
         impl Default for $name {
             fn default() -> Self {
-                $name([P25Codeword::default(); $len])
+                $name([$crate::coding::galois::Codeword::default(); $len])
             }
         }
 
         impl Clone for $name {
             fn clone(&self) -> Self {
-                let mut coefs = [P25Codeword::default(); $len];
-                coefs.copy_from_slice(&self.0[..]);
-                $name(coefs)
+                *self
             }
         }
 
         impl std::ops::Deref for $name {
-            type Target = [P25Codeword];
+            type Target = [$crate::coding::galois::Codeword<$field>];
             fn deref(&self) -> &Self::Target {
                 &self.0[..]
             }
@@ -282,7 +380,7 @@ macro_rules! impl_polynomial_coefs {
     };
 }
 
-/// Polynomial with P25's GF(2<sup>6</sup>) codewords as coefficients.
+/// Polynomial with Galois field codewords as coefficients.
 #[derive(Copy, Clone)]
 pub struct Polynomial<P: PolynomialCoefs> {
     /// Coefficients of the polynomial. The maximum degree span in the algorithm is [0,
@@ -299,7 +397,7 @@ impl<P: PolynomialCoefs> Polynomial<P> {
     ///
     /// The resulting polynomial has the form p(x) = c<sub>0</sub> + c<sub>1</sub>x + ···
     /// + c<sub>k</sub>x<sup>k</sup>.
-    pub fn new<T: Iterator<Item = P25Codeword>>(mut init: T) -> Self {
+    pub fn new<T: Iterator<Item = Codeword<P::Field>>>(mut init: T) -> Self {
         // Start with all zero coefficients and add in the given ones.
         let mut coefs = P::default();
         init.collect_slice_exhaust(&mut coefs[..]);
@@ -321,7 +419,7 @@ impl<P: PolynomialCoefs> Polynomial<P> {
     }
 
     /// Retrieve the degree-0 coefficient, c<sub>0</sub>.
-    pub fn constant(&self) -> P25Codeword {
+    pub fn constant(&self) -> Codeword<P::Field> {
         self.coefs[self.start]
     }
 
@@ -347,34 +445,34 @@ impl<P: PolynomialCoefs> Polynomial<P> {
     pub fn shift(mut self) -> Polynomial<P> {
         assert!(self.constant().zero());
 
-        self.coefs[self.start] = P25Codeword::default();
+        self.coefs[self.start] = Codeword::default();
         self.start += 1;
         self
     }
 
     /// Retrieve the coefficient at the given absolute index into the storage buffer, or 0
     /// if the index is out of bounds.
-    fn get(&self, idx: usize) -> P25Codeword {
+    fn get(&self, idx: usize) -> Codeword<P::Field> {
         match self.coefs.get(idx) {
             Some(&c) => c,
-            None => P25Codeword::default(),
+            None => Codeword::default(),
         }
     }
 
     /// Retrieve the coefficient c<sub>i</sub> associated with the x<sup>i</sup> term.
     ///
     /// If i > deg(p(x)), 0 is returned.
-    pub fn coef(&self, i: usize) -> P25Codeword {
+    pub fn coef(&self, i: usize) -> Codeword<P::Field> {
         self.get(self.start + i)
     }
 
     /// Evaluate p(x), substituting in the given x.
-    pub fn eval(&self, x: P25Codeword) -> P25Codeword {
+    pub fn eval(&self, x: Codeword<P::Field>) -> Codeword<P::Field> {
         // This uses Horner's method which, unlike the naive method, doesn't require a
         // call to `pow()` at each term.
         self.iter()
             .rev()
-            .fold(P25Codeword::default(), |s, &coef| s * x + coef)
+            .fold(Codeword::default(), |s, &coef| s * x + coef)
     }
 
     /// Truncate the polynomial so that deg(p(x)) ≤ d, where d is the given degree.
@@ -382,7 +480,7 @@ impl<P: PolynomialCoefs> Polynomial<P> {
     /// This is a O(n) operation.
     pub fn truncate(mut self, deg: usize) -> Polynomial<P> {
         for i in (self.start + deg + 1)..self.coefs.len() {
-            self.coefs[i] = P25Codeword::default();
+            self.coefs[i] = Codeword::default();
         }
 
         self
@@ -391,10 +489,10 @@ impl<P: PolynomialCoefs> Polynomial<P> {
     /// Compute the formal derivative p'(x).
     pub fn deriv(mut self) -> Polynomial<P> {
         for i in self.start..self.coefs.len() {
-            self.coefs[i] = if (i - self.start) % 2 == 0 {
+            self.coefs[i] = if (i - self.start).is_multiple_of(2) {
                 self.get(i + 1)
             } else {
-                P25Codeword::default()
+                Codeword::default()
             };
         }
 
@@ -412,7 +510,7 @@ impl<P: PolynomialCoefs> Default for Polynomial<P> {
 /// Provides a slice of coefficients starting at the degree-0 term, [c<sub>0</sub>,
 /// c<sub>1</sub>, ...].
 impl<P: PolynomialCoefs> std::ops::Deref for Polynomial<P> {
-    type Target = [P25Codeword];
+    type Target = [Codeword<P::Field>];
     fn deref(&self) -> &Self::Target {
         &self.coefs[self.start..]
     }
@@ -442,10 +540,10 @@ impl<P: PolynomialCoefs> std::ops::Add for Polynomial<P> {
 }
 
 /// Scale polynomial by a codeword.
-impl<P: PolynomialCoefs> std::ops::Mul<P25Codeword> for Polynomial<P> {
+impl<P: PolynomialCoefs> std::ops::Mul<Codeword<P::Field>> for Polynomial<P> {
     type Output = Polynomial<P>;
 
-    fn mul(mut self, rhs: P25Codeword) -> Self::Output {
+    fn mul(mut self, rhs: Codeword<P::Field>) -> Self::Output {
         for coef in self.coefs.iter_mut() {
             *coef = *coef * rhs;
         }
@@ -500,6 +598,69 @@ mod test {
         assert_eq!(TestCoefs::syndromes(), 22);
     }
 
+    define_field!(Gf4, 0b111);
+    define_field!(Gf256, 0x11d);
+    define_field!(Gf65536, 0x1100b);
+
+    #[test]
+    fn test_p25_field() {
+        // Tables from the P25 specification.
+        const CODEWORDS: [u16; 63] = [
+            0b000001, 0b000010, 0b000100, 0b001000, 0b010000, 0b100000, 0b000011, 0b000110,
+            0b001100, 0b011000, 0b110000, 0b100011, 0b000101, 0b001010, 0b010100, 0b101000,
+            0b010011, 0b100110, 0b001111, 0b011110, 0b111100, 0b111011, 0b110101, 0b101001,
+            0b010001, 0b100010, 0b000111, 0b001110, 0b011100, 0b111000, 0b110011, 0b100101,
+            0b001001, 0b010010, 0b100100, 0b001011, 0b010110, 0b101100, 0b011011, 0b110110,
+            0b101111, 0b011101, 0b111010, 0b110111, 0b101101, 0b011001, 0b110010, 0b100111,
+            0b001101, 0b011010, 0b110100, 0b101011, 0b010101, 0b101010, 0b010111, 0b101110,
+            0b011111, 0b111110, 0b111111, 0b111101, 0b111001, 0b110001, 0b100001,
+        ];
+
+        const POWERS: [usize; 63] = [
+            0, 1, 6, 2, 12, 7, 26, 3, 32, 13, 35, 8, 48, 27, 18, 4, 24, 33, 16, 14, 52, 36, 54, 9,
+            45, 49, 38, 28, 41, 19, 56, 5, 62, 25, 11, 34, 31, 17, 47, 15, 23, 53, 51, 37, 44, 55,
+            40, 10, 61, 46, 30, 50, 22, 39, 43, 29, 60, 42, 21, 20, 59, 57, 58,
+        ];
+
+        assert_eq!(P25Field::size(), 63);
+
+        for (pow, &word) in CODEWORDS.iter().enumerate() {
+            assert_eq!(P25Field::codeword(pow), word);
+        }
+
+        for (word, &pow) in POWERS.iter().enumerate() {
+            assert_eq!(P25Field::power(word), pow);
+        }
+
+        assert!(P25Field::valid_codeword(0b111111));
+        assert!(!P25Field::valid_codeword(0b1000000));
+    }
+
+    #[test]
+    fn test_define_field() {
+        assert_eq!(Gf4::size(), 3);
+        assert_eq!(Codeword::<Gf4>::for_power(2), 0b11);
+        assert_eq!(Codeword::<Gf4>::for_power(3), 0b01);
+
+        assert_eq!(Gf256::size(), 255);
+        assert_eq!(Codeword::<Gf256>::for_power(8), 0x1d);
+        assert_eq!(Codeword::<Gf256>::new(0x53) * Codeword::new(0xca), 0x8f);
+
+        for w in 1..=255 {
+            let c = Codeword::<Gf256>::new(w);
+            assert_eq!(c * c.invert(), 1);
+            assert_eq!(Codeword::<Gf256>::for_power(c.power().unwrap()), c);
+        }
+
+        assert_eq!(Gf65536::size(), 65535);
+        assert_eq!(Codeword::<Gf65536>::for_power(16), 0x100b);
+        assert_eq!(Codeword::<Gf65536>::for_power(65535), 1);
+
+        let c = Codeword::<Gf65536>::new(0xbeef);
+        assert_eq!(c / c, 1);
+        assert_eq!(c * c.invert(), 1);
+    }
+
     #[test]
     fn test_for_power() {
         assert!(P25Codeword::for_power(0) == 0b000001);
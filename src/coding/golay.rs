@@ -1,7 +1,85 @@
 //! Encoding and decoding of the (23, 12, 7) standard, (24, 12, 8) extended, and (18, 6,
 //! 8) shortened Golay codes described by P25.
 
-pub use cai_golay::{extended, standard};
+use crate::coding::correction::{bit_corrections, Correction};
+use crate::error::DecodeError;
+
+/// Encoding and decoding of the (23, 12, 7) code.
+pub mod standard {
+    use super::*;
+
+    /// Encode the given 12 data bits into a 23-bit codeword.
+    pub fn encode(data: u16) -> u32 {
+        cai_golay::standard::encode(data)
+    }
+
+    /// Try to decode the given 23-bit word to the nearest codeword, correcting up to 3
+    /// errors.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 12
+    /// data bits and `err` is the number of corrected bits. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(word: u32) -> Result<(u16, usize), DecodeError> {
+        decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+    }
+
+    /// Try to decode the given 23-bit word to the nearest codeword, correcting up to 3
+    /// errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 12
+    /// data bits and `fixes` describes each corrected bit. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode_detailed(word: u32) -> Result<(u16, Vec<Correction>), DecodeError> {
+        match cai_golay::standard::decode(word) {
+            Some((data, _)) => Ok((data, bit_corrections((encode(data) ^ word).into(), 23))),
+            None => Err(DecodeError::Uncorrectable),
+        }
+    }
+
+    impl_block_code! {
+        /// The (23, 12, 7) perfect Golay code, correcting any pattern of up to 3 bit errors.
+        Code: 23, 12, 7, u16, u32, encode, decode
+    }
+}
+
+/// Encoding and decoding of the (24, 12, 8) code.
+pub mod extended {
+    use super::*;
+
+    /// Encode the given 12 data bits into a 24-bit codeword.
+    pub fn encode(data: u16) -> u32 {
+        cai_golay::extended::encode(data)
+    }
+
+    /// Try to decode the given 24-bit word to the nearest codeword, correcting up to 3
+    /// errors and detecting 4 errors.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 12
+    /// data bits and `err` is the number of corrected bits. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(word: u32) -> Result<(u16, usize), DecodeError> {
+        decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+    }
+
+    /// Try to decode the given 24-bit word to the nearest codeword, correcting up to 3
+    /// errors and detecting 4 errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 12
+    /// data bits and `fixes` describes each corrected bit. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode_detailed(word: u32) -> Result<(u16, Vec<Correction>), DecodeError> {
+        match cai_golay::extended::decode(word) {
+            Some((data, _)) => Ok((data, bit_corrections((encode(data) ^ word).into(), 24))),
+            None => Err(DecodeError::Uncorrectable),
+        }
+    }
+
+    impl_block_code! {
+        /// The (24, 12, 8) Golay code, the standard code with an overall parity bit
+        /// appended, protecting each pair of TDULC link control hexbits.
+        Code: 24, 12, 8, u16, u32, encode, decode
+    }
+}
 
 /// Encoding and decoding of the (18, 6, 8) code.
 pub mod shortened {
@@ -16,26 +94,96 @@ pub mod shortened {
     /// Try to decode the given 18-bit word to the nearest codeword, correcting up to 3
     /// errors.
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 6
-    /// data bits and `err` is the number of corrected bits. Otherwise, return `None` to
-    /// indicate an unrecoverable error.
-    pub fn decode(word: u32) -> Option<(u8, usize)> {
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 6
+    /// data bits and `err` is the number of corrected bits. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(word: u32) -> Result<(u8, usize), DecodeError> {
+        decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+    }
+
+    /// Try to decode the given 18-bit word to the nearest codeword, correcting up to 3
+    /// errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 6
+    /// data bits and `fixes` describes each corrected bit. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode_detailed(word: u32) -> Result<(u8, Vec<Correction>), DecodeError> {
         assert_eq!(word >> 18, 0);
 
-        extended::decode(word).and_then(|(data, err)| {
-            if data >> 6 == 0 {
-                Some((data as u8, err))
-            } else {
-                None
-            }
-        })
+        match extended::decode(word) {
+            Ok((data, _)) if data >> 6 == 0 => Ok((
+                data as u8,
+                bit_corrections((encode(data as u8) ^ word).into(), 18),
+            )),
+            Ok(_) => Err(DecodeError::ShortenedOverflow),
+            Err(err) => Err(err),
+        }
+    }
+
+    impl_block_code! {
+        /// The (18, 6, 8) Golay code, shortened from the extended code to protect each
+        /// hexbit of the HDU header.
+        Code: 18, 6, 8, u8, u32, encode, decode
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::identity_op)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_standard() {
+        let w = 0b1010_1010_1010;
+        let e = standard::encode(w);
+        assert_eq!(e >> 11, w as u32);
+
+        assert_eq!(standard::decode(e), Ok((w, 0)));
+        assert_eq!(
+            standard::decode(e ^ 0b100_0000_0000_0000_0000_0001),
+            Ok((w, 2))
+        );
+        assert_eq!(
+            standard::decode(e ^ 0b000_0000_0000_0000_0000_0111),
+            Ok((w, 3))
+        );
+
+        let (data, fixes) = standard::decode_detailed(e ^ (1 << 22 | 1 << 5)).unwrap();
+        assert_eq!(data, w);
+        assert_eq!(
+            fixes.iter().map(|c| c.index).collect::<Vec<_>>(),
+            vec![0, 17]
+        );
+    }
+
+    #[test]
+    fn test_extended() {
+        let w = 0b1010_1010_1010;
+        let e = extended::encode(w);
+        assert_eq!(e >> 12, w as u32);
+
+        assert_eq!(extended::decode(e), Ok((w, 0)));
+        assert_eq!(
+            extended::decode(e ^ 0b1000_0000_0000_0000_0000_0001),
+            Ok((w, 2))
+        );
+        assert_eq!(
+            extended::decode(e ^ 0b0000_0000_0000_0000_0000_0111),
+            Ok((w, 3))
+        );
+        assert_eq!(
+            extended::decode(e ^ 0b0000_0000_0000_0000_0000_1111),
+            Err(DecodeError::Uncorrectable)
+        );
+
+        let (data, fixes) = extended::decode_detailed(e ^ (1 << 23 | 1 << 12 | 1)).unwrap();
+        assert_eq!(data, w);
+        assert_eq!(
+            fixes.iter().map(|c| c.index).collect::<Vec<_>>(),
+            vec![0, 11, 23]
+        );
+    }
+
     #[test]
     fn test_shortened() {
         assert_eq!(shortened::encode(0), 0);
@@ -48,51 +196,51 @@ mod test {
         let e = shortened::encode(w);
         assert_eq!(e, 0b10_1010_0010_0011_0101);
 
-        assert_eq!(shortened::decode(e ^ 0b100000000000000001), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b010000000000000010), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b001000000000000100), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000100000000001000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000010000000010000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000001000000100000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000100001000000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000010010000000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000001100000000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000000101000000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000001000100000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000010000010000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000100000001000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000001000000000100), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000010000000000010), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000100000000000001), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b001000000000000100), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b010000000000010000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b100000000000100000), Some((w, 2)));
-
-        assert_eq!(shortened::decode(e ^ 0b111000000000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b011100000000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b001110000000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000111000000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000011100000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000001110000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000111000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000011100000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000001110000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000111000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000011100000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000001110000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000111000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000011100), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000001110), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000000111), Some((w, 3)));
-
-        assert_eq!(shortened::decode(e ^ 0b000000000000000000), Some((w, 0)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000000001), Some((w, 1)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000000011), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b000000000000000111), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b000100000000000000), Some((w, 1)));
-        assert_eq!(shortened::decode(e ^ 0b001100000000000000), Some((w, 2)));
-        assert_eq!(shortened::decode(e ^ 0b011100000000000000), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b001100000000000010), Some((w, 3)));
-        assert_eq!(shortened::decode(e ^ 0b001000000000000110), Some((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b100000000000000001), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b010000000000000010), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b001000000000000100), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000100000000001000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000010000000010000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000001000000100000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000100001000000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000010010000000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000001100000000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000000101000000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000001000100000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000010000010000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000100000001000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000001000000000100), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000010000000000010), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000100000000000001), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b001000000000000100), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b010000000000010000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b100000000000100000), Ok((w, 2)));
+
+        assert_eq!(shortened::decode(e ^ 0b111000000000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b011100000000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b001110000000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000111000000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000011100000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000001110000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000111000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000011100000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000001110000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000111000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000011100000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000001110000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000111000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000011100), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000001110), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000000111), Ok((w, 3)));
+
+        assert_eq!(shortened::decode(e ^ 0b000000000000000000), Ok((w, 0)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000000001), Ok((w, 1)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000000011), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b000000000000000111), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b000100000000000000), Ok((w, 1)));
+        assert_eq!(shortened::decode(e ^ 0b001100000000000000), Ok((w, 2)));
+        assert_eq!(shortened::decode(e ^ 0b011100000000000000), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b001100000000000010), Ok((w, 3)));
+        assert_eq!(shortened::decode(e ^ 0b001000000000000110), Ok((w, 3)));
     }
 }
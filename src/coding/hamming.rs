@@ -7,6 +7,9 @@
 use binfield_matrix::{matrix_mul, matrix_mul_systematic};
 use num_traits::PrimInt;
 
+use crate::coding::correction::{bit_corrections, Correction};
+use crate::error::DecodeError;
+
 /// Encoding and decoding of the (15, 11, 3) code.
 pub mod standard {
     use super::*;
@@ -20,12 +23,28 @@ pub mod standard {
     /// Try to decode the given 15-bit word to the nearest codeword, correcting up to 1
     /// error.
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 11
-    /// data bits and `err` is the number of corrected bits. Otherwise, return `None` to
-    /// indicate an unrecoverable error.
-    pub fn decode(word: u16) -> Option<(u16, usize)> {
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 11
+    /// data bits and `err` is the number of corrected bits. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(word: u16) -> Result<(u16, usize), DecodeError> {
+        decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+    }
+
+    /// Try to decode the given 15-bit word to the nearest codeword, correcting up to 1
+    /// error.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 11
+    /// data bits and `fixes` describes the corrected bit, if any. Otherwise, return an
+    /// `Err` describing the unrecoverable error.
+    pub fn decode_detailed(word: u16) -> Result<(u16, Vec<Correction>), DecodeError> {
         assert!(word >> 15 == 0);
-        super::decode(word, PAR, LOCATIONS).map(|(w, n)| (w >> 4, n))
+        super::decode(word, PAR, LOCATIONS)
+            .map(|(w, loc)| (w >> 4, bit_corrections(loc.into(), 15)))
+    }
+
+    impl_block_code! {
+        /// The (15, 11, 3) Hamming code, correcting a single bit error.
+        Code: 15, 11, 3, u16, u16, encode, decode
     }
 
     /// Generator matrix from the standard, without identity part.
@@ -73,12 +92,29 @@ pub mod shortened {
     /// Try to decode the given 10-bit word to the nearest codeword, correcting up to 1
     /// error.
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 6
-    /// data bits and `err` is the number of corrected bits. Otherwise, return `None` to
-    /// indicate an unrecoverable error.
-    pub fn decode(word: u16) -> Option<(u8, usize)> {
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 6
+    /// data bits and `err` is the number of corrected bits. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(word: u16) -> Result<(u8, usize), DecodeError> {
+        decode_detailed(word).map(|(data, fixes)| (data, fixes.len()))
+    }
+
+    /// Try to decode the given 10-bit word to the nearest codeword, correcting up to 1
+    /// error.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 6
+    /// data bits and `fixes` describes the corrected bit, if any. Otherwise, return an
+    /// `Err` describing the unrecoverable error.
+    pub fn decode_detailed(word: u16) -> Result<(u8, Vec<Correction>), DecodeError> {
         assert!(word >> 10 == 0);
-        super::decode(word, PAR, LOCATIONS).map(|(w, n)| ((w >> 4) as u8, n))
+        super::decode(word, PAR, LOCATIONS)
+            .map(|(w, loc)| ((w >> 4) as u8, bit_corrections(loc.into(), 10)))
+    }
+
+    impl_block_code! {
+        /// The (10, 6, 3) Hamming code, shortened from the standard code to protect
+        /// each hexbit of the LDU1 link control and LDU2 encryption sync words.
+        Code: 10, 6, 3, u8, u16, encode, decode
     }
 
     const GEN: &[u8] = &[0b111001, 0b110101, 0b101110, 0b011110];
@@ -105,23 +141,27 @@ pub mod shortened {
     ];
 }
 
-fn decode<T: PrimInt>(word: T, par: &[T], locs: &[T]) -> Option<(T, usize)> {
+/// Try to correct a single error in the given word using the given parity-check matrix
+/// and syndrome-to-location table, where a zero location indicates a syndrome that
+/// doesn't map to any bit of the word.
+///
+/// On success, return `Ok((word, loc))`, where `word` is the corrected word and `loc` is
+/// the corrected bit, or zero if no error was found.
+fn decode<T: PrimInt>(word: T, par: &[T], locs: &[T]) -> Result<(T, T), DecodeError> {
     let s: usize = matrix_mul(word, par);
 
     if s == 0 {
-        return Some((word, 0));
+        return Ok((word, T::zero()));
     }
 
-    locs.get(s).and_then(|&loc| {
-        if loc == T::zero() {
-            None
-        } else {
-            Some((word ^ loc, 1))
-        }
-    })
+    match locs.get(s) {
+        Some(&loc) if loc != T::zero() => Ok((word ^ loc, loc)),
+        _ => Err(DecodeError::OutOfBounds),
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
 mod test {
     use super::*;
 
@@ -131,15 +171,24 @@ mod test {
         assert_eq!(standard::encode(0b11111111111), 0b11111111111_1111);
 
         for w in 0..1 << 11 {
-            assert_eq!(standard::decode(standard::encode(w)), Some((w, 0)));
+            assert_eq!(standard::decode(standard::encode(w)), Ok((w, 0)));
         }
 
         let w = standard::encode(0b10101010101);
         assert_eq!(w, 0b10101010101_0101);
-        assert_eq!(standard::decode(w), Some((0b10101010101, 0)));
+        assert_eq!(standard::decode(w), Ok((0b10101010101, 0)));
 
         for i in 0..15 {
-            assert_eq!(standard::decode(w ^ 1 << i), Some((0b10101010101, 1)));
+            assert_eq!(standard::decode(w ^ 1 << i), Ok((0b10101010101, 1)));
+
+            let (_, fixes) = standard::decode_detailed(w ^ 1 << i).unwrap();
+            assert_eq!(
+                fixes,
+                vec![Correction {
+                    index: 14 - i,
+                    pattern: 1
+                }]
+            );
         }
 
         for (i, j) in (0..15).zip(0..15) {
@@ -158,15 +207,15 @@ mod test {
         assert_eq!(shortened::encode(0b111111), 0b111111_0000);
 
         for w in 0..1 << 6 {
-            assert_eq!(shortened::decode(shortened::encode(w)), Some((w, 0)));
+            assert_eq!(shortened::decode(shortened::encode(w)), Ok((w, 0)));
         }
 
         let w = shortened::encode(0b101010);
         assert_eq!(w, 0b101010_0110);
-        assert_eq!(shortened::decode(w), Some((0b101010, 0)));
+        assert_eq!(shortened::decode(w), Ok((0b101010, 0)));
 
         for i in 0..10 {
-            assert_eq!(shortened::decode(w ^ 1 << i), Some((0b101010, 1)));
+            assert_eq!(shortened::decode(w ^ 1 << i), Ok((0b101010, 1)));
         }
 
         for (i, j) in (0..10).zip(0..10) {
@@ -177,5 +226,13 @@ mod test {
                 assert_eq!(n, 1);
             }
         }
+
+        // Five of the 16 syndromes don't map to a bit in the shortened word.
+        assert_eq!(
+            (0..1 << 10)
+                .filter(|&w| shortened::decode(w) == Err(DecodeError::OutOfBounds))
+                .count(),
+            5 * 64
+        );
     }
 }
@@ -0,0 +1,208 @@
+//! Interleaving of coded symbols, which spreads burst errors across a block so they
+//! appear as scattered errors to the decoder.
+
+/// The fixed 98-dibit interleaver applied to every trellis-coded P25 data block, such as
+/// TSBKs, PDU headers, and PDU data blocks.
+///
+/// Interleaving and deinterleaving are generic over the symbol type, so they can operate
+/// on hard `bits::Dibit` decisions as well as soft-decision values.
+pub mod p25_data {
+    /// Number of dibits in an interleaved block.
+    pub const DIBITS: usize = 98;
+
+    /// Source index of each interleaved dibit, where the dibit at index i of the
+    /// interleaved block is coded dibit `INTERLEAVE[i]`.
+    const INTERLEAVE: [usize; DIBITS] = [
+        0, 1, 8, 9, 16, 17, 24, 25, 32, 33, 40, 41, 48, 49, 56, 57, 64, 65, 72, 73, 80, 81, 88, 89,
+        96, 97, 2, 3, 10, 11, 18, 19, 26, 27, 34, 35, 42, 43, 50, 51, 58, 59, 66, 67, 74, 75, 82,
+        83, 90, 91, 4, 5, 12, 13, 20, 21, 28, 29, 36, 37, 44, 45, 52, 53, 60, 61, 68, 69, 76, 77,
+        84, 85, 92, 93, 6, 7, 14, 15, 22, 23, 30, 31, 38, 39, 46, 47, 54, 55, 62, 63, 70, 71, 78,
+        79, 86, 87, 94, 95,
+    ];
+
+    /// Source index of each deinterleaved dibit, the inverse of `INTERLEAVE`.
+    const DEINTERLEAVE: [usize; DIBITS] = [
+        0, 1, 26, 27, 50, 51, 74, 75, 2, 3, 28, 29, 52, 53, 76, 77, 4, 5, 30, 31, 54, 55, 78, 79,
+        6, 7, 32, 33, 56, 57, 80, 81, 8, 9, 34, 35, 58, 59, 82, 83, 10, 11, 36, 37, 60, 61, 84, 85,
+        12, 13, 38, 39, 62, 63, 86, 87, 14, 15, 40, 41, 64, 65, 88, 89, 16, 17, 42, 43, 66, 67, 90,
+        91, 18, 19, 44, 45, 68, 69, 92, 93, 20, 21, 46, 47, 70, 71, 94, 95, 22, 23, 48, 49, 72, 73,
+        96, 97, 24, 25,
+    ];
+
+    /// Interleave the given block of coded symbols in place.
+    pub fn interleave<T: Copy>(buf: &mut [T; DIBITS]) {
+        permute(buf, &INTERLEAVE)
+    }
+
+    /// Deinterleave the given block of received symbols in place.
+    pub fn deinterleave<T: Copy>(buf: &mut [T; DIBITS]) {
+        permute(buf, &DEINTERLEAVE)
+    }
+
+    /// Rearrange the given block so each symbol i is taken from source index `table[i]`.
+    fn permute<T: Copy>(buf: &mut [T; DIBITS], table: &[usize; DIBITS]) {
+        let src = *buf;
+
+        for (dest, &idx) in buf.iter_mut().zip(table.iter()) {
+            *dest = src[idx];
+        }
+    }
+
+    /// Iterator that rearranges each consecutive block of 98 symbols from a source. The
+    /// source must be a multiple of 98 symbols.
+    pub struct Permuter<T: Iterator> {
+        /// Source of symbols.
+        src: T,
+        /// Source index of each yielded symbol within the block.
+        table: &'static [usize; DIBITS],
+        /// Current buffered block.
+        buf: Vec<T::Item>,
+        /// Index of the next symbol to yield within the block.
+        idx: usize,
+    }
+
+    impl<T: Iterator> Permuter<T>
+    where
+        T::Item: Copy,
+    {
+        /// Construct a new `Permuter` that interleaves the symbols from the given source.
+        pub fn interleave(src: T) -> Permuter<T> {
+            Permuter::new(src, &INTERLEAVE)
+        }
+
+        /// Construct a new `Permuter` that deinterleaves the symbols from the given
+        /// source.
+        pub fn deinterleave(src: T) -> Permuter<T> {
+            Permuter::new(src, &DEINTERLEAVE)
+        }
+
+        fn new(src: T, table: &'static [usize; DIBITS]) -> Permuter<T> {
+            Permuter {
+                src,
+                table,
+                buf: Vec::with_capacity(DIBITS),
+                idx: DIBITS,
+            }
+        }
+    }
+
+    impl<T: Iterator> Iterator for Permuter<T>
+    where
+        T::Item: Copy,
+    {
+        type Item = T::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.idx == DIBITS {
+                self.buf.clear();
+                self.buf.extend((&mut self.src).take(DIBITS));
+
+                // It's okay if there are no more source symbols here, because we're on a
+                // block boundary.
+                if self.buf.is_empty() {
+                    return None;
+                }
+
+                assert!(self.buf.len() == DIBITS, "incomplete block");
+
+                self.idx = 0;
+            }
+
+            let next = self.buf[self.table[self.idx]];
+            self.idx += 1;
+
+            Some(next)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::p25_data::*;
+    use crate::bits::Dibit;
+    use crate::coding::trellis::Deviation;
+
+    fn block() -> [usize; DIBITS] {
+        let mut buf = [0; DIBITS];
+
+        for (i, dest) in buf.iter_mut().enumerate() {
+            *dest = i;
+        }
+
+        buf
+    }
+
+    #[test]
+    fn test_interleave() {
+        let mut buf = block();
+        interleave(&mut buf);
+
+        assert_eq!(&buf[..6], &[0, 1, 8, 9, 16, 17]);
+        assert_eq!(&buf[24..28], &[96, 97, 2, 3]);
+        assert_eq!(&buf[96..], &[94, 95]);
+
+        let mut seen = [false; DIBITS];
+
+        for &idx in buf.iter() {
+            assert!(!seen[idx]);
+            seen[idx] = true;
+        }
+
+        deinterleave(&mut buf);
+        assert_eq!(&buf[..], &block()[..]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut buf = block();
+        interleave(&mut buf);
+
+        let src = block()
+            .iter()
+            .chain(block().iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let inter = Permuter::interleave(src.iter().cloned()).collect::<Vec<_>>();
+
+        assert_eq!(inter.len(), 2 * DIBITS);
+        assert_eq!(&inter[..DIBITS], &buf[..]);
+        assert_eq!(&inter[DIBITS..], &buf[..]);
+
+        let deinter = Permuter::deinterleave(inter.into_iter()).collect::<Vec<_>>();
+        assert_eq!(deinter, src);
+
+        let dibits = Permuter::interleave(block().iter().map(|&i| Dibit::new(i as u8 & 0b11)))
+            .collect::<Vec<_>>();
+        assert_eq!(dibits[2], Dibit::new(0b00));
+        assert_eq!(dibits[3], Dibit::new(0b01));
+    }
+
+    #[test]
+    fn test_soft() {
+        let mut buf = [0.0f32; DIBITS];
+
+        for (i, dest) in buf.iter_mut().enumerate() {
+            *dest = i as f32 / 10.0;
+        }
+
+        let orig = buf;
+
+        interleave(&mut buf);
+        assert_eq!(buf[2], 0.8);
+
+        deinterleave(&mut buf);
+        assert_eq!(buf, orig);
+
+        let mut soft = [Deviation(0.0); DIBITS];
+        soft[8] = Deviation(-3.0);
+
+        interleave(&mut soft);
+        assert_eq!(soft[2].0, -3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_incomplete() {
+        Permuter::interleave(0..DIBITS + 1).for_each(drop);
+    }
+}
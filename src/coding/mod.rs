@@ -17,10 +17,10 @@
 //!    (http://downloads.bbc.co.uk/rd/pubs/whp/ whp-pdf-files/WHP031.pdf), Clarke, BBC,
 //!    2002.
 //! 8. ["Lecture 18: Decoding of Nonbinary BCH and RS Codes"]
-//!     (http://www.site.uottawa.ca/~damours/courses/ELG_5372/Lecture18.pdf), D'Amours,
-//!     University of Ottowa.
+//!    (http://www.site.uottawa.ca/~damours/courses/ELG_5372/Lecture18.pdf), D'Amours,
+//!    University of Ottowa.
 //! 9. ["EE 387, Notes 19"](http://web.stanford.edu/class/ee387/handouts/notes19.pdf),
-//!     Gill, Stanford University.
+//!    Gill, Stanford University.
 //! 10. ["EE 387, Notes 20"](http://web.stanford.edu/class/ee387/handouts/notes20.pdf),
 //!     Gill, Stanford University.
 //! 11. ["Implementing Reed-Solomon"]
@@ -34,11 +34,16 @@
 
 #[macro_use]
 pub mod galois;
+#[macro_use]
+pub mod block;
 
 pub mod bch;
 pub mod bmcf;
+pub mod correction;
+pub mod crc;
 pub mod cyclic;
 pub mod golay;
 pub mod hamming;
+pub mod interleave;
 pub mod reed_solomon;
 pub mod trellis;
@@ -1,5 +1,7 @@
 //! Encoding and decoding of the (24, 12, 13) short, (24, 16, 9) medium, and (36, 20, 17)
-//! long Reed-Solomon codes described by P25.
+//! long Reed-Solomon codes described by P25, the shortened and punctured codes of P25
+//! Phase 2, along with a configurable codec for byte-wise codes such as those of CCSDS,
+//! DVB, and QR codes.
 //!
 //! These algorithms are sourced from *Coding Theory and Cryptography: The Essentials*,
 //! Hankerson, Hoffman, et al, 2000.
@@ -10,11 +12,17 @@ use collect_slice::CollectSlice;
 
 use crate::bits::Hexbit;
 use crate::coding::bmcf;
-use crate::coding::galois::{P25Codeword, Polynomial, PolynomialCoefs};
+use crate::coding::correction::Correction;
+use crate::coding::galois::{
+    Codeword, GaloisField, P25Codeword, P25Field, Polynomial, PolynomialCoefs,
+};
+use crate::error::DecodeError;
 
 /// Encoding and decoding of the (24, 12, 13) code.
 pub mod short {
     use crate::bits::Hexbit;
+    use crate::coding::correction::Correction;
+    use crate::error::DecodeError;
 
     /// Transpose of G_LC.
     const GEN: [[u8; 12]; 12] = [
@@ -66,18 +74,55 @@ pub mod short {
     /// Try to decode the given 24-hexbit word to the nearest codeword, correcting up to 6
     /// hexbit errors (up to 36 bit errors.)
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 12
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 12
     /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
-    /// `None` to indicate an unrecoverable error.
-    pub fn decode(buf: &mut [Hexbit; 24]) -> Option<(&[Hexbit], usize)> {
-        super::decode::<super::ShortCoefs>(buf)
-            .map(move |(poly, err)| (super::extract_data(poly, &mut buf[..12]), err))
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode(buf: &mut [Hexbit; 24]) -> Result<(&[Hexbit], usize), DecodeError> {
+        decode_with_erasures(buf, &[])
+    }
+
+    /// Try to decode the given 24-hexbit word to the nearest codeword, treating the
+    /// hexbits at the given buffer indexes as erasures. Any combination of e errors and f
+    /// erasures with 2e + f ≤ 12 can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 12
+    /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode_with_erasures<'a>(
+        buf: &'a mut [Hexbit; 24],
+        erasures: &[usize],
+    ) -> Result<(&'a [Hexbit], usize), DecodeError> {
+        super::decode::<super::ShortCoefs>(buf, erasures)
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..12]), fixes.len()))
+    }
+
+    /// Try to decode the given 24-hexbit word to the nearest codeword, correcting up to 6
+    /// hexbit errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 12
+    /// data hexbits and `fixes` describes each corrected hexbit. Otherwise, return an
+    /// `Err` describing the unrecoverable error.
+    pub fn decode_detailed(
+        buf: &mut [Hexbit; 24],
+    ) -> Result<(&[Hexbit], Vec<Correction>), DecodeError> {
+        super::decode::<super::ShortCoefs>(buf, &[])
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..12]), fixes))
+    }
+
+    impl_block_code! {
+        /// The (24, 12, 13) Reed-Solomon code protecting the LDU1 and TDULC link control
+        /// words.
+        Code: 24, 12, 13, [Hexbit; 12], [Hexbit; 24],
+        |data| super::encode_block(data, encode),
+        |word| super::decode_block(word, decode)
     }
 }
 
 /// Encoding and decoding of the (24, 16, 9) code.
 pub mod medium {
     use crate::bits::Hexbit;
+    use crate::coding::correction::Correction;
+    use crate::error::DecodeError;
 
     /// Transpose of G_ES.
     const GEN: [[u8; 16]; 8] = [
@@ -125,18 +170,54 @@ pub mod medium {
     /// Try to decode the given 24-hexbit word to the nearest codeword, correcting up to 4
     /// hexbit errors (up to 24 bit errors.)
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 16
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 16
+    /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode(buf: &mut [Hexbit; 24]) -> Result<(&[Hexbit], usize), DecodeError> {
+        decode_with_erasures(buf, &[])
+    }
+
+    /// Try to decode the given 24-hexbit word to the nearest codeword, treating the
+    /// hexbits at the given buffer indexes as erasures. Any combination of e errors and f
+    /// erasures with 2e + f ≤ 8 can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 16
     /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
-    /// `None` to indicate an unrecoverable error.
-    pub fn decode(buf: &mut [Hexbit; 24]) -> Option<(&[Hexbit], usize)> {
-        super::decode::<super::MedCoefs>(buf)
-            .map(move |(poly, err)| (super::extract_data(poly, &mut buf[..16]), err))
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode_with_erasures<'a>(
+        buf: &'a mut [Hexbit; 24],
+        erasures: &[usize],
+    ) -> Result<(&'a [Hexbit], usize), DecodeError> {
+        super::decode::<super::MedCoefs>(buf, erasures)
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..16]), fixes.len()))
+    }
+
+    /// Try to decode the given 24-hexbit word to the nearest codeword, correcting up to 4
+    /// hexbit errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 16
+    /// data hexbits and `fixes` describes each corrected hexbit. Otherwise, return an
+    /// `Err` describing the unrecoverable error.
+    pub fn decode_detailed(
+        buf: &mut [Hexbit; 24],
+    ) -> Result<(&[Hexbit], Vec<Correction>), DecodeError> {
+        super::decode::<super::MedCoefs>(buf, &[])
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..16]), fixes))
+    }
+
+    impl_block_code! {
+        /// The (24, 16, 9) Reed-Solomon code protecting the LDU2 encryption sync word.
+        Code: 24, 16, 9, [Hexbit; 16], [Hexbit; 24],
+        |data| super::encode_block(data, encode),
+        |word| super::decode_block(word, decode)
     }
 }
 
 /// Encoding and decoding of the (36, 20, 17) code.
 pub mod long {
     use crate::bits::Hexbit;
+    use crate::coding::correction::Correction;
+    use crate::error::DecodeError;
 
     /// Transpose of P_HDR.
     const GEN: [[u8; 20]; 16] = [
@@ -216,13 +297,75 @@ pub mod long {
     /// Try to decode the given 36-hexbit word to the nearest codeword, correcting up to 8
     /// hexbit errors (up to 48 bit errors.)
     ///
-    /// If decoding was successful, return `Some((data, err))`, where `data` is the 20
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 20
+    /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode(buf: &mut [Hexbit; 36]) -> Result<(&[Hexbit], usize), DecodeError> {
+        decode_with_erasures(buf, &[])
+    }
+
+    /// Try to decode the given 36-hexbit word to the nearest codeword, treating the
+    /// hexbits at the given buffer indexes as erasures. Any combination of e errors and f
+    /// erasures with 2e + f ≤ 16 can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the 20
     /// data hexbits and `err` is the number of corrected hexbits. Otherwise, return
-    /// `None` to indicate an unrecoverable error.
-    pub fn decode(buf: &mut [Hexbit; 36]) -> Option<(&[Hexbit], usize)> {
-        super::decode::<super::LongCoefs>(buf)
-            .map(move |(poly, err)| (super::extract_data(poly, &mut buf[..20]), err))
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode_with_erasures<'a>(
+        buf: &'a mut [Hexbit; 36],
+        erasures: &[usize],
+    ) -> Result<(&'a [Hexbit], usize), DecodeError> {
+        super::decode::<super::LongCoefs>(buf, erasures)
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..20]), fixes.len()))
     }
+
+    /// Try to decode the given 36-hexbit word to the nearest codeword, correcting up to 8
+    /// hexbit errors.
+    ///
+    /// If decoding was successful, return `Ok((data, fixes))`, where `data` is the 20
+    /// data hexbits and `fixes` describes each corrected hexbit. Otherwise, return an
+    /// `Err` describing the unrecoverable error.
+    pub fn decode_detailed(
+        buf: &mut [Hexbit; 36],
+    ) -> Result<(&[Hexbit], Vec<Correction>), DecodeError> {
+        super::decode::<super::LongCoefs>(buf, &[])
+            .map(move |(poly, fixes)| (super::extract_data(poly, &mut buf[..20]), fixes))
+    }
+
+    impl_block_code! {
+        /// The (36, 20, 17) Reed-Solomon code protecting the HDU header.
+        Code: 36, 20, 17, [Hexbit; 20], [Hexbit; 36],
+        |data| super::encode_block(data, encode),
+        |word| super::decode_block(word, decode)
+    }
+}
+
+/// Encode the given k data hexbits into an n-hexbit codeword with the given in-place
+/// encoder.
+fn encode_block<const K: usize, const N: usize>(
+    data: [Hexbit; K],
+    encode: fn(&mut [Hexbit; N]),
+) -> [Hexbit; N] {
+    let mut buf = [Hexbit::default(); N];
+    buf[..K].copy_from_slice(&data);
+    encode(&mut buf);
+    buf
+}
+
+/// In-place decoder over an n-hexbit buffer, returning the data hexbits and the number
+/// of corrected hexbits.
+type BlockDecode<const N: usize> = fn(&mut [Hexbit; N]) -> Result<(&[Hexbit], usize), DecodeError>;
+
+/// Try to decode the given n-hexbit word with the given in-place decoder, copying out
+/// the k data hexbits.
+fn decode_block<const K: usize, const N: usize>(
+    mut word: [Hexbit; N],
+    decode: BlockDecode<N>,
+) -> Result<([Hexbit; K], usize), DecodeError> {
+    let (data, err) = decode(&mut word)?;
+    let mut buf = [Hexbit::default(); K];
+    buf.copy_from_slice(data);
+    Ok((buf, err))
 }
 
 /// Encode the given data with the given generator matrix and place the resulting parity
@@ -235,45 +378,130 @@ where
         row.iter()
             .zip(data.iter())
             .fold(P25Codeword::default(), |s, (&col, &d)| {
-                s + P25Codeword::new(d.bits()) * P25Codeword::new(col)
+                s + P25Codeword::new(d.bits().into()) * P25Codeword::new(col.into())
             })
-            .bits()
+            .bits() as u8
     })
     .map(Hexbit::new)
     .collect_slice_checked(parity);
 }
 
-/// Try to fix any errors in the given word.
+/// Try to fix any errors and erasures in the given word, where each erasure is given as
+/// an index into the word. Duplicate erasure indexes are counted once.
 ///
-/// On success, return `Some((poly, err))`, where `poly` is the polynomial representation
+/// On success, return `Ok((poly, fixes))`, where `poly` is the polynomial representation
 /// of the corrected word (with the last data symbol as the degree-0 coefficient) and
-/// `err` is the number of corrected hexbit symbols. Otherwise, return `None` to indicate
-/// an unrecoverable error.
-fn decode<P: PolynomialCoefs>(word: &[Hexbit]) -> Option<(Polynomial<P>, usize)> {
+/// `fixes` describes each corrected hexbit symbol. Otherwise, return an `Err` describing
+/// the unrecoverable error.
+fn decode<P: PolynomialCoefs<Field = P25Field>>(
+    word: &[Hexbit],
+    erasures: &[usize],
+) -> Result<(Polynomial<P>, Vec<Correction>), DecodeError> {
     // In the polynomial representation, the first received symbol corresponds to the
     // coefficient of the highest-degree term.
-    let mut poly = Polynomial::new(word.iter().rev().map(|&b| P25Codeword::new(b.bits())));
+    let mut poly: Polynomial<P> = Polynomial::new(
+        word.iter()
+            .rev()
+            .map(|&b| P25Codeword::new(b.bits().into())),
+    );
+
+    let locs = erasure_degrees(word.len(), erasures)?;
+
+    // The P25 generator polynomials have roots α, α^2, ..., α^2t.
+    correct::<P>(&mut poly[..word.len()], locs.into_iter(), 1, 1).map(|fixes| (poly, fixes))
+}
+
+/// Convert the given erasure indexes into a word of the given length to the degrees of
+/// their terms in the polynomial representation, dropping any duplicates.
+///
+/// Return `Err(OutOfBounds)` if any index lies outside the word.
+fn erasure_degrees(len: usize, erasures: &[usize]) -> Result<Vec<usize>, DecodeError> {
+    let mut degs = erasures
+        .iter()
+        .map(|&idx| {
+            if idx < len {
+                Ok(len - idx - 1)
+            } else {
+                Err(DecodeError::OutOfBounds)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    degs.sort_unstable();
+    degs.dedup();
+
+    Ok(degs)
+}
+
+/// Try to fix any errors and erasures in the given polynomial-form word r(x), where
+/// `word[i]` is the coefficient of x<sup>i</sup> and each erasure is given as the degree
+/// of its term. The code's generator polynomial must have the consecutive roots
+/// α<sup>bs</sup>, α<sup>(b+1)s</sup>, ..., α<sup>(b+2t-1)s</sup> for the given first
+/// root power b and root spacing s.
+///
+/// On success, return `Ok(fixes)`, where `fixes` describes each corrected symbol, indexed
+/// from the highest-degree term. Otherwise, return an `Err` describing the unrecoverable
+/// error.
+fn correct<P: PolynomialCoefs>(
+    word: &mut [Codeword<P::Field>],
+    erasures: impl Iterator<Item = usize>,
+    first: usize,
+    spacing: usize,
+) -> Result<Vec<Correction>, DecodeError> {
+    let size = P::Field::size();
+
+    // With X = β^i for an error at degree i and β = α^s, each syndrome has the form
+    // s_j = Σ e X^(b+j-1) = Σ (e X^(b-1)) X^j, which is the form expected by the
+    // decoder with the scaled patterns e X^(b-1) and the error locations X = α^(si).
+    let unspace = (1..=size)
+        .find(|&p| p * spacing % size == 1)
+        .expect("root spacing not coprime to field size");
+    let unscale = (size + 1 - first % size) % size;
+
+    let locs = erasures.map(|deg| deg * spacing % size);
+
+    bmcf::Errors::with_erasures(syndromes::<P>(word, first, spacing), locs).and_then(|(_, errs)| {
+        let mut fixes = Vec::new();
 
-    bmcf::Errors::new(syndromes(&poly)).and_then(|(nerr, errs)| {
         for (loc, pat) in errs {
-            match poly.get_mut(loc) {
+            let deg = loc * unspace % size;
+            let pat = pat * Codeword::for_power(loc * unscale);
+
+            match word.get_mut(deg) {
                 Some(coef) => *coef = *coef + pat,
-                None => return None,
+                None => return Err(DecodeError::OutOfBounds),
+            }
+
+            // An erased symbol may have been received correctly.
+            if !pat.zero() {
+                fixes.push(Correction {
+                    index: word.len() - deg - 1,
+                    pattern: pat.bits(),
+                });
             }
         }
 
-        Some((poly, nerr))
+        Ok(fixes)
     })
 }
 
-/// Generate the syndrome polynomial s(x) from the given received word r(x).
+/// Generate the syndrome polynomial s(x) from the given polynomial-form received word
+/// r(x), with generator roots given by the first root power b and root spacing s.
 ///
 /// The resulting polynomial has the form s(x) = s<sub>1</sub> + s<sub>2</sub>x + ··· +
-/// s<sub>2t</sub>x<sup>2t</sup>, where s<sub>i</sub> = r(α<sup>i</sup>).
-fn syndromes<P: PolynomialCoefs>(word: &Polynomial<P>) -> Polynomial<P> {
-    Polynomial::new((1..=P::syndromes()).map(|p| {
-        // Compute r(α^p).
-        word.eval(P25Codeword::for_power(p))
+/// s<sub>2t</sub>x<sup>2t</sup>, where s<sub>i</sub> = r(α<sup>(b+i-1)s</sup>).
+fn syndromes<P: PolynomialCoefs>(
+    word: &[Codeword<P::Field>],
+    first: usize,
+    spacing: usize,
+) -> Polynomial<P> {
+    Polynomial::new((0..P::syndromes()).map(|i| {
+        let x = Codeword::for_power((first + i) * spacing);
+
+        // Compute r(x) with Horner's method.
+        word.iter()
+            .rev()
+            .fold(Codeword::default(), |s, &coef| s * x + coef)
     }))
 }
 
@@ -281,18 +509,437 @@ fn syndromes<P: PolynomialCoefs>(word: &Polynomial<P>) -> Polynomial<P> {
 /// given slice.
 fn extract_data<P>(poly: Polynomial<P>, data: &mut [Hexbit]) -> &[Hexbit]
 where
-    P: PolynomialCoefs,
+    P: PolynomialCoefs<Field = P25Field>,
 {
     poly.iter()
         .rev()
-        .map(|coef| Hexbit::new(coef.bits()))
+        .map(|coef| Hexbit::new(coef.bits() as u8))
         .collect_slice_fill(data);
     data
 }
 
+/// Reed-Solomon code over a Galois field of up to 256 elements, operating on byte
+/// symbols.
+///
+/// The (n, k) code parameters and the generator roots are chosen when the codec is
+/// created, and the distance d = n - k + 1 and the field are given by the coefficient
+/// storage `P`. The generator polynomial has the n - k consecutive roots
+/// α<sup>bs</sup>, α<sup>(b+1)s</sup>, ..., α<sup>(b+n-k-1)s</sup>, for first root
+/// power b and root spacing s. When n is less than the field size, the code is shortened
+/// with the missing high-degree symbols taken as zero.
+///
+/// Codewords are systematic, with the k data symbols at the start of the buffer followed
+/// by the n - k parity symbols, and the first symbol corresponding to the coefficient of
+/// the highest-degree term.
+#[derive(Copy, Clone)]
+pub struct RsCodec<P: PolynomialCoefs> {
+    /// Number of symbols in a codeword: n.
+    n: usize,
+    /// Power b of the first generator root.
+    first: usize,
+    /// Spacing s between the powers of consecutive generator roots.
+    spacing: usize,
+    /// Generator polynomial g(x).
+    gen: Polynomial<P>,
+}
+
+impl<P: PolynomialCoefs> RsCodec<P> {
+    /// Create a new (n, k) codec with the given first generator root power and root
+    /// spacing.
+    pub fn new(n: usize, k: usize, first: usize, spacing: usize) -> Self {
+        let size = P::Field::size();
+
+        P::default().validate();
+        // Symbols must fit in a byte.
+        assert!(size <= 255);
+        // Codewords can't be longer than the field allows.
+        assert!(n <= size);
+        // The generator polynomial must have one root per syndrome.
+        assert!(k > 0 && k < n && n - k == P::syndromes());
+        // The generator roots must all be distinct.
+        assert!((1..=size).any(|p| p * spacing % size == 1));
+
+        let gen = (first..first + P::syndromes()).fold(Polynomial::unit_power(0), |g, p| {
+            g * Polynomial::new(
+                [Codeword::for_power(p * spacing), Codeword::for_power(0)]
+                    .iter()
+                    .cloned(),
+            )
+        });
+
+        RsCodec {
+            n,
+            first,
+            spacing,
+            gen,
+        }
+    }
+
+    /// Number of symbols in a codeword: n.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of data symbols in a codeword: k.
+    pub fn k(&self) -> usize {
+        self.n - P::syndromes()
+    }
+
+    /// Generator polynomial g(x) of the code.
+    pub fn generator(&self) -> &Polynomial<P> {
+        &self.gen
+    }
+
+    /// Encode the first k data symbols in the given n-symbol buffer, placing the parity
+    /// symbols at the end of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any data symbol lies outside the field, such as a symbol of 64 or more
+    /// for GF(2<sup>6</sup>).
+    pub fn encode(&self, buf: &mut [u8]) {
+        assert_eq!(buf.len(), self.n);
+
+        let nsyn = P::syndromes();
+        let (data, parity) = buf.split_at_mut(self.k());
+
+        // Compute the remainder of m(x)x^(n-k) divided by g(x) with the usual feedback
+        // shift register, starting with the highest-degree data symbol.
+        let mut rem = P::default();
+
+        for &d in data.iter() {
+            let feedback = Codeword::new(d.into()) + rem[nsyn - 1];
+
+            for i in (1..nsyn).rev() {
+                rem[i] = rem[i - 1] + feedback * self.gen.coef(i);
+            }
+
+            rem[0] = feedback * self.gen.coef(0);
+        }
+
+        rem[..nsyn]
+            .iter()
+            .rev()
+            .map(|coef| coef.bits() as u8)
+            .collect_slice_checked(parity);
+    }
+
+    /// Try to decode the given n-symbol word to the nearest codeword, correcting up to
+    /// t = (n - k) / 2 symbol errors.
+    ///
+    /// If decoding was successful, return `Ok((data, err))`, where `data` is the k
+    /// data symbols and `err` is the number of corrected symbols. Otherwise, return
+    /// an `Err` describing the unrecoverable error.
+    pub fn decode<'a>(&self, buf: &'a mut [u8]) -> Result<(&'a [u8], usize), DecodeError> {
+        self.decode_with_erasures(buf, &[])
+    }
+
+    /// Try to decode the given n-symbol word to the nearest codeword, correcting up to
+    /// t = (n - k) / 2 symbol errors.
+    ///
+    /// If decoding was successful, the whole buffer is corrected in place and
+    /// `Ok((data, fixes))` is returned, where `data` is the k data symbols and `fixes`
+    /// describes each corrected symbol. Otherwise, return an `Err` describing the
+    /// unrecoverable error.
+    pub fn decode_detailed<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> Result<(&'a [u8], Vec<Correction>), DecodeError> {
+        self.correct(buf, &[])
+            .map(move |fixes| (&buf[..self.k()], fixes))
+    }
+
+    /// Try to decode the given n-symbol word to the nearest codeword, treating the
+    /// symbols at the given buffer indexes as erasures. Any combination of e errors and f
+    /// erasures with 2e + f ≤ n - k can be corrected.
+    ///
+    /// If decoding was successful, the whole buffer is corrected in place and
+    /// `Ok((data, err))` is returned, where `data` is the k data symbols and `err` is the
+    /// number of corrected symbols. Otherwise, return an `Err` describing the
+    /// unrecoverable error.
+    pub fn decode_with_erasures<'a>(
+        &self,
+        buf: &'a mut [u8],
+        erasures: &[usize],
+    ) -> Result<(&'a [u8], usize), DecodeError> {
+        self.correct(buf, erasures)
+            .map(move |fixes| (&buf[..self.k()], fixes.len()))
+    }
+
+    /// Try to correct the given n-symbol word in place, treating the symbols at the given
+    /// buffer indexes as erasures.
+    fn correct(&self, buf: &mut [u8], erasures: &[usize]) -> Result<Vec<Correction>, DecodeError> {
+        assert_eq!(buf.len(), self.n);
+
+        if !buf.iter().all(|&b| P::Field::valid_codeword(b.into())) {
+            return Err(DecodeError::InvalidSymbol);
+        }
+
+        let mut coefs = [Codeword::<P::Field>::default(); 255];
+        let word = &mut coefs[..self.n];
+
+        buf.iter()
+            .rev()
+            .map(|&b| Codeword::new(b.into()))
+            .collect_slice_checked(word);
+
+        let locs = erasure_degrees(self.n, erasures)?;
+        let fixes = correct::<P>(word, locs.into_iter(), self.first, self.spacing)?;
+
+        word.iter()
+            .rev()
+            .map(|coef| coef.bits() as u8)
+            .collect_slice_checked(buf);
+
+        Ok(fixes)
+    }
+}
+
+/// Standard Reed-Solomon codes over GF(2<sup>8</sup>).
+pub mod gf256 {
+    use super::RsCodec;
+
+    define_field!(
+        /// GF(2<sup>8</sup>) with primitive polynomial x<sup>8</sup> + x<sup>4</sup> +
+        /// x<sup>3</sup> + x<sup>2</sup> + 1, as used by DVB and QR codes.
+        Gf256Field,
+        0x11d
+    );
+
+    define_field!(
+        /// GF(2<sup>8</sup>) with primitive polynomial x<sup>8</sup> + x<sup>7</sup> +
+        /// x<sup>2</sup> + x + 1, as used by CCSDS.
+        CcsdsField,
+        0x187
+    );
+
+    impl_polynomial_coefs!(pub CcsdsCoefs: CcsdsField, 33);
+    impl_polynomial_coefs!(pub DvbCoefs: Gf256Field, 17);
+    impl_polynomial_coefs!(pub Qr10Coefs: Gf256Field, 11);
+    impl_polynomial_coefs!(pub Qr16Coefs: Gf256Field, 17);
+    impl_polynomial_coefs!(pub Qr22Coefs: Gf256Field, 23);
+    impl_polynomial_coefs!(pub Qr28Coefs: Gf256Field, 29);
+
+    /// The (255, 223, 33) code of CCSDS 131.0-B, with generator roots
+    /// α<sup>11·112</sup>, ..., α<sup>11·143</sup>.
+    ///
+    /// Symbols are in the conventional representation, so data in the dual-basis
+    /// representation used on air must be converted before encoding and decoding.
+    pub fn ccsds() -> RsCodec<CcsdsCoefs> {
+        RsCodec::new(255, 223, 112, 11)
+    }
+
+    /// The (204, 188, 17) code of DVB (ETSI EN 300 421), shortened from the (255, 239)
+    /// code with generator roots α<sup>0</sup>, ..., α<sup>15</sup>.
+    pub fn dvb() -> RsCodec<DvbCoefs> {
+        RsCodec::new(204, 188, 0, 1)
+    }
+
+    /// The (26, 16, 11) code of a version 1-M QR code block.
+    pub fn qr_1m() -> RsCodec<Qr10Coefs> {
+        RsCodec::new(26, 16, 0, 1)
+    }
+
+    /// The (44, 34, 11) code of a version 2-L QR code block.
+    pub fn qr_2l() -> RsCodec<Qr10Coefs> {
+        RsCodec::new(44, 34, 0, 1)
+    }
+
+    /// The (44, 28, 17) code of a version 2-M QR code block.
+    pub fn qr_2m() -> RsCodec<Qr16Coefs> {
+        RsCodec::new(44, 28, 0, 1)
+    }
+
+    /// The (44, 22, 23) code of a version 2-Q QR code block.
+    pub fn qr_2q() -> RsCodec<Qr22Coefs> {
+        RsCodec::new(44, 22, 0, 1)
+    }
+
+    /// The (44, 16, 29) code of a version 2-H QR code block.
+    pub fn qr_2h() -> RsCodec<Qr28Coefs> {
+        RsCodec::new(44, 16, 0, 1)
+    }
+}
+
+/// Encoding and decoding of the P25 Phase 2 codes, which are shortened and punctured from
+/// the (63, 35, 29) code over the same field as the Phase 1 codes, with generator roots
+/// α, α<sup>2</sup>, ..., α<sup>28</sup>.
+///
+/// Each code is shortened by taking the leading data symbols of the (63, 35, 29) code as
+/// zero and punctured by dropping the trailing parity symbols. The decoder restores the
+/// punctured symbols as erasures, so a code with p punctured symbols can correct any
+/// combination of e errors and f further erasures with 2e + f ≤ 28 - p.
+pub mod phase2 {
+    use super::{EssCoefs, FacchCoefs, RsCodec, SacchCoefs};
+    use crate::bits::Hexbit;
+    use crate::coding::correction::Correction;
+    use crate::coding::galois::{P25Field, PolynomialCoefs};
+    use crate::error::DecodeError;
+
+    /// Number of parity symbols in the (63, 35, 29) code.
+    const PARITY: usize = 28;
+
+    /// Declare the module for the (n, k, d) code, with the given coefficient storage
+    /// holding the k + 28 symbols of the shortened but unpunctured codeword.
+    macro_rules! phase2_code {
+        ($(#[$attr:meta])* $name:ident, $n:literal, $k:literal, $d:literal, $coefs:ident) => {
+            $(#[$attr])*
+            pub mod $name {
+                use crate::bits::Hexbit;
+                use crate::coding::correction::Correction;
+                use crate::error::DecodeError;
+
+                #[doc = concat!("Calculate the parity hexbits for the first ", $k, " data")]
+                #[doc = concat!("hexbits in the given ", $n, "-hexbit buffer, placing the")]
+                /// parity hexbits at the end of the buffer.
+                pub fn encode(buf: &mut [Hexbit; $n]) {
+                    super::encode::<super::$coefs>(buf, $k)
+                }
+
+                #[doc = concat!("Try to decode the given ", $n, "-hexbit word to the nearest")]
+                #[doc = concat!("codeword, correcting up to e hexbit errors with 2e < ", $d, ".")]
+                ///
+                #[doc = concat!("If decoding was successful, return `Ok((data, err))`, where")]
+                #[doc = concat!("`data` is the ", $k, " data hexbits and `err` is the number")]
+                /// of corrected hexbits. Otherwise, return an `Err` describing the
+                /// unrecoverable error.
+                pub fn decode(buf: &mut [Hexbit; $n]) -> Result<(&[Hexbit], usize), DecodeError> {
+                    decode_with_erasures(buf, &[])
+                }
+
+                #[doc = concat!("Try to decode the given ", $n, "-hexbit word to the nearest")]
+                /// codeword, treating the hexbits at the given buffer indexes as erasures.
+                /// Any combination of e errors and f erasures with
+                #[doc = concat!("2e + f < ", $d, " can be corrected.")]
+                ///
+                #[doc = concat!("If decoding was successful, return `Ok((data, err))`, where")]
+                #[doc = concat!("`data` is the ", $k, " data hexbits and `err` is the number")]
+                /// of corrected hexbits. Otherwise, return an `Err` describing the
+                /// unrecoverable error.
+                pub fn decode_with_erasures<'a>(
+                    buf: &'a mut [Hexbit; $n],
+                    erasures: &[usize],
+                ) -> Result<(&'a [Hexbit], usize), DecodeError> {
+                    super::decode::<super::$coefs>(buf, $k, erasures)
+                        .map(move |fixes| (&buf[..$k], fixes.len()))
+                }
+
+                #[doc = concat!("Try to decode the given ", $n, "-hexbit word to the nearest")]
+                #[doc = concat!("codeword, correcting up to e hexbit errors with 2e < ", $d, ".")]
+                ///
+                #[doc = concat!("If decoding was successful, return `Ok((data, fixes))`, where")]
+                #[doc = concat!("`data` is the ", $k, " data hexbits and `fixes` describes")]
+                /// each corrected hexbit. Otherwise, return an `Err` describing the
+                /// unrecoverable error.
+                pub fn decode_detailed(
+                    buf: &mut [Hexbit; $n],
+                ) -> Result<(&[Hexbit], Vec<Correction>), DecodeError> {
+                    super::decode::<super::$coefs>(buf, $k, &[])
+                        .map(move |fixes| (&buf[..$k], fixes))
+                }
+
+                impl_block_code! {
+                    #[doc = concat!("The (", $n, ", ", $k, ", ", $d, ") code, for use through")]
+                    /// the `BlockCode` trait.
+                    Code: $n, $k, $d, [Hexbit; $k], [Hexbit; $n],
+                    |data| super::super::encode_block(data, encode),
+                    |word| super::super::decode_block(word, decode)
+                }
+            }
+        };
+    }
+
+    phase2_code!(
+        /// Encoding and decoding of the (52, 30, 23) code protecting the fast associated
+        /// control channel (FACCH), with 6 punctured parity symbols.
+        facch, 52, 30, 23, FacchCoefs
+    );
+
+    phase2_code!(
+        /// Encoding and decoding of the (46, 26, 21) code protecting the slow associated
+        /// control channel (SACCH), with 8 punctured parity symbols.
+        sacch, 46, 26, 21, SacchCoefs
+    );
+
+    // The LCCH code carries the same 26 data symbols as the SACCH code, so both are
+    // punctured from the same 54-symbol shortened codeword and share its coefficient
+    // storage.
+    phase2_code!(
+        /// Encoding and decoding of the (45, 26, 20) code protecting the logical control
+        /// channel (LCCH), with 9 punctured parity symbols.
+        lcch, 45, 26, 20, SacchCoefs
+    );
+
+    phase2_code!(
+        /// Encoding and decoding of the (44, 16, 29) code protecting the encryption sync
+        /// signal (ESS), with no punctured parity symbols.
+        ess, 44, 16, 29, EssCoefs
+    );
+
+    /// Encode the first k data hexbits in the given buffer, placing the unpunctured
+    /// parity hexbits at the end of the buffer. The coefficient storage `P` must hold
+    /// exactly k + 28 symbols.
+    fn encode<P: PolynomialCoefs<Field = P25Field>>(buf: &mut [Hexbit], k: usize) {
+        let n = buf.len();
+
+        let mut word = [0; 63];
+        let word = &mut word[..k + PARITY];
+
+        for (dest, hexbit) in word.iter_mut().zip(buf[..k].iter()) {
+            *dest = hexbit.bits();
+        }
+
+        RsCodec::<P>::new(k + PARITY, k, 1, 1).encode(word);
+
+        // Only the leading parity hexbits are transmitted.
+        for (dest, &sym) in buf[k..].iter_mut().zip(word[k..n].iter()) {
+            *dest = Hexbit::new(sym);
+        }
+    }
+
+    /// Try to correct the given received word with k data hexbits in place, treating the
+    /// hexbits at the given buffer indexes, along with each punctured hexbit, as
+    /// erasures. The coefficient storage `P` must hold exactly k + 28 symbols.
+    ///
+    /// On success, return `Ok(fixes)`, where `fixes` describes each corrected hexbit of
+    /// the received word. Otherwise, return an `Err` describing the unrecoverable error.
+    fn decode<P: PolynomialCoefs<Field = P25Field>>(
+        buf: &mut [Hexbit],
+        k: usize,
+        erasures: &[usize],
+    ) -> Result<Vec<Correction>, DecodeError> {
+        let n = buf.len();
+
+        let mut word = [Hexbit::default(); 63];
+        let word = &mut word[..k + PARITY];
+        word[..n].copy_from_slice(buf);
+
+        // Erasures must fall within the received word.
+        if erasures.iter().any(|&idx| idx >= n) {
+            return Err(DecodeError::OutOfBounds);
+        }
+
+        // The punctured symbols follow the received symbols.
+        let erasures = erasures
+            .iter()
+            .cloned()
+            .chain(n..word.len())
+            .collect::<Vec<_>>();
+
+        let (poly, fixes) = super::decode::<P>(word, &erasures)?;
+        super::extract_data(poly, buf);
+
+        // Filling in the punctured symbols isn't a correction to the received word.
+        Ok(fixes.into_iter().filter(|fix| fix.index < n).collect())
+    }
+}
+
 impl_polynomial_coefs!(ShortCoefs, 13, 24);
 impl_polynomial_coefs!(MedCoefs, 9, 24);
 impl_polynomial_coefs!(LongCoefs, 17, 36);
+impl_polynomial_coefs!(FacchCoefs, 29, 58);
+impl_polynomial_coefs!(SacchCoefs, 29, 54);
+impl_polynomial_coefs!(EssCoefs, 29, 44);
 
 #[cfg(test)]
 mod test {
@@ -546,7 +1193,7 @@ mod test {
             Hexbit::new(0),
         ];
 
-        assert_eq!(dec, Some((&exp[..], 6)));
+        assert_eq!(dec, Ok((&exp[..], 6)));
     }
 
     #[test]
@@ -584,7 +1231,7 @@ mod test {
             Hexbit::new(0),
         ];
 
-        assert_eq!(dec, Some((&exp[..], 4)));
+        assert_eq!(dec, Ok((&exp[..], 4)));
     }
 
     #[test]
@@ -631,7 +1278,7 @@ mod test {
             Hexbit::new(0),
         ];
 
-        assert_eq!(dec, Some((&exp[..], 8)));
+        assert_eq!(dec, Ok((&exp[..], 8)));
 
         let exp = [
             Hexbit::new(0o77),
@@ -672,7 +1319,7 @@ mod test {
             buf[i] = Hexbit::new(0);
             let dec = long::decode(&mut buf);
 
-            assert_eq!(dec, Some((&exp[..], 1)));
+            assert_eq!(dec, Ok((&exp[..], 1)));
         }
 
         // Test contiguous 48-bit error.
@@ -698,7 +1345,7 @@ mod test {
             buf[i + 7] = Hexbit::new(0);
             let dec = long::decode(&mut buf);
 
-            assert_eq!(dec, Some((&exp[..], 8)));
+            assert_eq!(dec, Ok((&exp[..], 8)));
         }
     }
 
@@ -746,7 +1393,7 @@ mod test {
             Hexbit::new(0),
             Hexbit::new(0),
         ];
-        assert_eq!(long::decode(&mut w), None);
+        assert_eq!(long::decode(&mut w), Err(DecodeError::RootCountMismatch));
 
         let mut w = [
             Hexbit::new(0),
@@ -786,7 +1433,7 @@ mod test {
             Hexbit::new(35),
             Hexbit::new(3),
         ];
-        assert_eq!(long::decode(&mut w), None);
+        assert_eq!(long::decode(&mut w), Err(DecodeError::RootCountMismatch));
 
         // More general unrecoverable words.
 
@@ -828,7 +1475,7 @@ mod test {
             Hexbit::new(51),
             Hexbit::new(38),
         ];
-        assert_eq!(long::decode(&mut w), None);
+        assert_eq!(long::decode(&mut w), Err(DecodeError::RootCountMismatch));
 
         let mut w = [
             Hexbit::new(0),
@@ -868,7 +1515,7 @@ mod test {
             Hexbit::new(0),
             Hexbit::new(20),
         ];
-        assert_eq!(long::decode(&mut w), None);
+        assert_eq!(long::decode(&mut w), Err(DecodeError::RootCountMismatch));
     }
 
     #[test]
@@ -935,7 +1582,7 @@ mod test {
             Hexbit::new(21),
             Hexbit::new(46),
         ];
-        assert_eq!(long::decode(&mut w), Some((&exp[..], 6)));
+        assert_eq!(long::decode(&mut w), Ok((&exp[..], 6)));
 
         let mut w = [
             Hexbit::new(0),
@@ -997,7 +1644,7 @@ mod test {
             Hexbit::new(18),
             Hexbit::new(63),
         ];
-        assert_eq!(long::decode(&mut w), Some((&exp[..], 3)));
+        assert_eq!(long::decode(&mut w), Ok((&exp[..], 3)));
 
         let mut w = [
             Hexbit::new(0),
@@ -1059,7 +1706,7 @@ mod test {
             Hexbit::new(18),
             Hexbit::new(63),
         ];
-        assert_eq!(long::decode(&mut w), Some((&exp[..], 0)));
+        assert_eq!(long::decode(&mut w), Ok((&exp[..], 0)));
 
         let mut w = [
             Hexbit::new(0),
@@ -1121,7 +1768,7 @@ mod test {
             Hexbit::new(18),
             Hexbit::new(63),
         ];
-        assert_eq!(long::decode(&mut w), Some((&exp[..], 4)));
+        assert_eq!(long::decode(&mut w), Ok((&exp[..], 4)));
     }
 
     #[test]
@@ -1156,7 +1803,7 @@ mod test {
             Hexbit::new(0),
         ];
 
-        assert_eq!(medium::decode(&mut w), None);
+        assert_eq!(medium::decode(&mut w), Err(DecodeError::OutOfBounds));
 
         // 6 errors, attempted access at location 61.
         let mut w = [
@@ -1186,6 +1833,481 @@ mod test {
             Hexbit::new(0),
         ];
 
-        assert_eq!(short::decode(&mut w), None);
+        assert_eq!(short::decode(&mut w), Err(DecodeError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_decode_erasures() {
+        let mut buf = [Hexbit::default(); 36];
+        [
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+        ]
+        .iter()
+        .map(|&b| Hexbit::new(b))
+        .collect_slice(&mut buf[..]);
+
+        long::encode(&mut buf);
+        let orig = buf;
+
+        // 16 erasures and no errors.
+        let erasures = [0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30];
+        let mut w = orig;
+        for &idx in erasures.iter() {
+            w[idx] = Hexbit::new(0o77 ^ w[idx].bits());
+        }
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..20], 16))
+        );
+
+        // 4 errors and 8 erasures, where two of the erased symbols are actually correct.
+        let erasures = [1, 5, 9, 13, 17, 21, 25, 35];
+        let mut w = orig;
+        for &idx in erasures[..6].iter() {
+            w[idx] = Hexbit::new(0o52 ^ w[idx].bits());
+        }
+        for &idx in [0, 19, 27, 34].iter() {
+            w[idx] = Hexbit::new(0o11 ^ w[idx].bits());
+        }
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..20], 10))
+        );
+
+        // 9 errors is beyond the error-only capability but recoverable with 6 erasures
+        // covering them.
+        let mut errs = orig;
+        for h in errs[3..12].iter_mut() {
+            *h = Hexbit::new(0o01 ^ h.bits());
+        }
+        let mut w = errs;
+        assert_eq!(long::decode(&mut w), Err(DecodeError::RootCountMismatch));
+        let mut w = errs;
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &[3, 4, 5, 6, 7, 8]),
+            Ok((&orig[..20], 9))
+        );
+
+        // More erasures than parity symbols.
+        let mut w = orig;
+        let erasures: Vec<usize> = (0..17).collect();
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &erasures),
+            Err(DecodeError::TooManyErasures)
+        );
+
+        // Erasures outside the word.
+        let mut w = orig;
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &[0, 36]),
+            Err(DecodeError::OutOfBounds)
+        );
+
+        // Duplicate erasures are only counted once.
+        let mut w = orig;
+        for &idx in [2, 9].iter() {
+            w[idx] = Hexbit::new(0o33 ^ w[idx].bits());
+        }
+        let erasures: Vec<usize> = (0..16).chain(2..10).collect();
+        assert_eq!(
+            long::decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..20], 2))
+        );
+
+        let mut buf = [Hexbit::default(); 24];
+        [0o77, 0, 0o12, 0, 0, 0o33, 0, 0, 0, 0o41, 0, 0]
+            .iter()
+            .map(|&b| Hexbit::new(b))
+            .collect_slice(&mut buf[..]);
+
+        short::encode(&mut buf);
+        let orig = buf;
+
+        // 3 errors and 6 erasures.
+        let mut w = orig;
+        for &idx in [0, 1, 2, 20, 21, 22, 4, 11, 23].iter() {
+            w[idx] = Hexbit::new(0o25 ^ w[idx].bits());
+        }
+        assert_eq!(
+            short::decode_with_erasures(&mut w, &[0, 1, 2, 20, 21, 22]),
+            Ok((&orig[..12], 9))
+        );
+
+        let mut buf = [Hexbit::default(); 24];
+        (0..16)
+            .map(|b| Hexbit::new(b * 3))
+            .collect_slice(&mut buf[..]);
+
+        medium::encode(&mut buf);
+        let orig = buf;
+
+        // 1 error and 6 erasures.
+        let mut w = orig;
+        for &idx in [7, 8, 9, 10, 11, 12, 15].iter() {
+            w[idx] = Hexbit::new(0o70 ^ w[idx].bits());
+        }
+        assert_eq!(
+            medium::decode_with_erasures(&mut w, &[7, 8, 9, 10, 11, 12]),
+            Ok((&orig[..16], 7))
+        );
+    }
+
+    #[test]
+    fn test_codec_qr() {
+        // Version 1-M "HELLO WORLD" example.
+        let mut buf = [0; 26];
+        buf[..16].copy_from_slice(&[
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ]);
+
+        let codec = gf256::qr_1m();
+        codec.encode(&mut buf);
+        assert_eq!(&buf[16..], &[196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+
+        let orig = buf;
+        assert_eq!(codec.decode(&mut buf), Ok((&orig[..16], 0)));
+
+        buf[0] ^= 0xff;
+        buf[9] ^= 0x01;
+        buf[17] ^= 0x42;
+        buf[25] ^= 0x80;
+        buf[20] ^= 0x11;
+        assert_eq!(codec.decode(&mut buf), Ok((&orig[..16], 5)));
+        assert_eq!(buf, orig);
+
+        let codec = gf256::qr_2h();
+        let mut buf = [0; 44];
+        (0..16).map(|b| b * 13 + 7).collect_slice(&mut buf[..]);
+        codec.encode(&mut buf);
+        let orig = buf;
+
+        for b in buf[10..24].iter_mut() {
+            *b ^= 0x5a;
+        }
+        assert_eq!(codec.decode(&mut buf), Ok((&orig[..16], 14)));
+    }
+
+    #[test]
+    fn test_codec_ccsds() {
+        let codec = gf256::ccsds();
+        assert_eq!((codec.n(), codec.k()), (255, 223));
+
+        // Roots are symmetric about α^(11·127.5), so the generator is palindromic.
+        let gen = codec.generator();
+        assert_eq!(gen.degree(), Some(32));
+        for i in 0..=32 {
+            assert_eq!(gen.coef(i), gen.coef(32 - i));
+        }
+        for p in 112..144 {
+            assert!(gen.eval(Codeword::for_power(11 * p)).zero());
+        }
+
+        let mut buf = [0; 255];
+        (0..223)
+            .map(|b| (b * 7 + 3) as u8)
+            .collect_slice(&mut buf[..]);
+        codec.encode(&mut buf);
+        let orig = buf;
+
+        let mut w = orig;
+        for idx in (0..255).step_by(16) {
+            w[idx] ^= 0xa5;
+        }
+        assert_eq!(codec.decode(&mut w), Ok((&orig[..223], 16)));
+        assert_eq!(w, orig);
+
+        // 8 errors and 16 erasures, 4 of which were received correctly.
+        let mut w = orig;
+        let erasures: Vec<usize> = (100..116).collect();
+        for &idx in erasures[4..].iter() {
+            w[idx] ^= 0x3c;
+        }
+        for idx in (0..240).step_by(30) {
+            w[idx] ^= 0x81;
+        }
+        assert_eq!(
+            codec.decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..223], 20))
+        );
+        assert_eq!(w, orig);
+    }
+
+    #[test]
+    fn test_codec_dvb() {
+        let codec = gf256::dvb();
+
+        let mut buf = [0; 204];
+        (0..188)
+            .map(|b| (b * 31 + 1) as u8)
+            .collect_slice(&mut buf[..]);
+        codec.encode(&mut buf);
+        let orig = buf;
+
+        let mut w = orig;
+        for idx in [0, 1, 50, 99, 187, 188, 200, 203].iter() {
+            w[*idx] ^= 0xff;
+        }
+        assert_eq!(codec.decode(&mut w), Ok((&orig[..188], 8)));
+
+        let mut w = orig;
+        let erasures: Vec<usize> = (0..16).map(|i| i * 12).collect();
+        for &idx in erasures.iter() {
+            w[idx] = 0;
+        }
+        assert_eq!(
+            codec
+                .decode_with_erasures(&mut w, &erasures)
+                .map(|(d, _)| d),
+            Ok(&orig[..188])
+        );
+        assert_eq!(w, orig);
+
+        // Too many erasures.
+        let erasures: Vec<usize> = (0..17).collect();
+        assert_eq!(
+            codec.decode_with_erasures(&mut w, &erasures),
+            Err(DecodeError::TooManyErasures)
+        );
+    }
+
+    #[test]
+    fn test_codec_invalid() {
+        let codec = RsCodec::<LongCoefs>::new(63, 47, 1, 1);
+
+        let mut buf = [0; 63];
+        (0..47)
+            .map(|b| (b * 5 % 64) as u8)
+            .collect_slice(&mut buf[..]);
+        codec.encode(&mut buf);
+        let orig = buf;
+
+        // Symbols beyond GF(2^6) can't be decoded.
+        let mut w = orig;
+        w[3] = 0x40;
+        assert_eq!(codec.decode(&mut w), Err(DecodeError::InvalidSymbol));
+
+        let mut w = orig;
+        w[60] = 0xff;
+        assert_eq!(codec.decode(&mut w), Err(DecodeError::InvalidSymbol));
+
+        // Erasures outside the word.
+        let mut w = orig;
+        assert_eq!(
+            codec.decode_with_erasures(&mut w, &[5, 63]),
+            Err(DecodeError::OutOfBounds)
+        );
+        assert_eq!(
+            codec.decode_with_erasures(&mut w, &[5, 5, 62]),
+            Ok((&orig[..47], 0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_codec_encode_invalid() {
+        let mut buf = [0; 63];
+        buf[3] = 0x40;
+        RsCodec::<LongCoefs>::new(63, 47, 1, 1).encode(&mut buf);
+    }
+
+    #[test]
+    fn test_decode_detailed() {
+        let mut buf = [Hexbit::default(); 36];
+        (0..20)
+            .map(|b| Hexbit::new(b * 2 + 5))
+            .collect_slice(&mut buf[..]);
+
+        long::encode(&mut buf);
+        let orig = buf;
+
+        buf[0] = Hexbit::new(buf[0].bits() ^ 0o77);
+        buf[19] = Hexbit::new(buf[19].bits() ^ 0o01);
+        buf[35] = Hexbit::new(buf[35].bits() ^ 0o42);
+
+        let (data, mut fixes) = long::decode_detailed(&mut buf).unwrap();
+        assert_eq!(data, &orig[..20]);
+
+        fixes.sort_by_key(|c| c.index);
+        assert_eq!(
+            fixes,
+            vec![
+                Correction {
+                    index: 0,
+                    pattern: 0o77
+                },
+                Correction {
+                    index: 19,
+                    pattern: 0o01
+                },
+                Correction {
+                    index: 35,
+                    pattern: 0o42
+                },
+            ]
+        );
+
+        let codec = gf256::ccsds();
+        let mut buf = [0; 255];
+        (0..223).map(|b| b as u8).collect_slice(&mut buf[..]);
+        codec.encode(&mut buf);
+        let orig = buf;
+
+        buf[7] ^= 0x80;
+        buf[254] ^= 0x3f;
+
+        let (data, mut fixes) = codec.decode_detailed(&mut buf).unwrap();
+        assert_eq!(data, &orig[..223]);
+        assert_eq!(buf, orig);
+
+        fixes.sort_by_key(|c| c.index);
+        assert_eq!(
+            fixes,
+            vec![
+                Correction {
+                    index: 7,
+                    pattern: 0x80
+                },
+                Correction {
+                    index: 254,
+                    pattern: 0x3f
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_phase2() {
+        FacchCoefs::default().validate();
+        SacchCoefs::default().validate();
+        EssCoefs::default().validate();
+
+        let mut buf = [Hexbit::default(); 52];
+        (0..30)
+            .map(|b| Hexbit::new(b * 2 + 1))
+            .collect_slice(&mut buf[..]);
+
+        phase2::facch::encode(&mut buf);
+        let orig = buf;
+
+        // The transmitted parity is the leading parity of the unpunctured codeword, which
+        // has roots α, ..., α^28.
+        let mut full = [0; 58];
+        for (dest, h) in full.iter_mut().zip(orig.iter()) {
+            *dest = h.bits();
+        }
+        let codec = RsCodec::<FacchCoefs>::new(58, 30, 1, 1);
+        let mut check = full;
+        codec.encode(&mut check);
+        assert_eq!(&check[..52], &full[..52]);
+
+        let poly =
+            Polynomial::<FacchCoefs>::new(check.iter().rev().map(|&b| P25Codeword::new(b.into())));
+        for i in 1..=28 {
+            assert_eq!(poly.eval(P25Codeword::for_power(i)), P25Codeword::default());
+        }
+
+        let mut w = orig;
+        assert_eq!(phase2::facch::decode(&mut w), Ok((&orig[..30], 0)));
+
+        // 11 errors.
+        let mut w = orig;
+        for &idx in [0, 4, 9, 13, 20, 27, 29, 33, 40, 47, 51].iter() {
+            w[idx] = Hexbit::new(0o17 ^ w[idx].bits());
+        }
+        let (data, fixes) = phase2::facch::decode_detailed(&mut w).unwrap();
+        assert_eq!(data, &orig[..30]);
+        assert_eq!(fixes.len(), 11);
+        assert!(fixes.iter().all(|f| f.index < 52 && f.pattern == 0o17));
+        assert_eq!(w, orig);
+
+        // 12 errors are too many.
+        let mut w = orig;
+        for h in w[10..22].iter_mut() {
+            *h = Hexbit::new(0o40 ^ h.bits());
+        }
+        assert_ne!(
+            phase2::facch::decode(&mut w).map(|(d, _)| d.to_vec()),
+            Ok(orig[..30].to_vec())
+        );
+
+        // 5 errors and 12 erasures.
+        let erasures = [1, 2, 3, 5, 8, 13, 21, 34, 44, 48, 49, 50];
+        let mut w = orig;
+        for &idx in erasures.iter().chain([0, 10, 30, 40, 51].iter()) {
+            w[idx] = Hexbit::new(0o63 ^ w[idx].bits());
+        }
+        assert_eq!(
+            phase2::facch::decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..30], 17))
+        );
+
+        // More erasures than the unpunctured parity symbols.
+        let mut w = orig;
+        let erasures: Vec<usize> = (0..23).collect();
+        assert_eq!(
+            phase2::facch::decode_with_erasures(&mut w, &erasures),
+            Err(DecodeError::TooManyErasures)
+        );
+
+        // Erasures can't fall on the punctured symbols, and duplicates count once.
+        let mut w = orig;
+        assert_eq!(
+            phase2::facch::decode_with_erasures(&mut w, &[3, 52]),
+            Err(DecodeError::OutOfBounds)
+        );
+        let erasures: Vec<usize> = (0..22).chain(0..22).collect();
+        assert_eq!(
+            phase2::facch::decode_with_erasures(&mut w, &erasures),
+            Ok((&orig[..30], 0))
+        );
+
+        let mut buf = [Hexbit::default(); 46];
+        (0..26)
+            .map(|b| Hexbit::new(63 - b))
+            .collect_slice(&mut buf[..]);
+        phase2::sacch::encode(&mut buf);
+        let orig = buf;
+
+        // 10 errors.
+        let mut w = orig;
+        for h in w.iter_mut().step_by(5) {
+            *h = Hexbit::new(0o01 ^ h.bits());
+        }
+        assert_eq!(phase2::sacch::decode(&mut w), Ok((&orig[..26], 10)));
+
+        let mut buf = [Hexbit::default(); 45];
+        (0..26).map(Hexbit::new).collect_slice(&mut buf[..]);
+        phase2::lcch::encode(&mut buf);
+        let orig = buf;
+
+        // The LCCH code is the SACCH code with one more punctured symbol.
+        let mut sacch = [Hexbit::default(); 46];
+        sacch[..26].copy_from_slice(&orig[..26]);
+        phase2::sacch::encode(&mut sacch);
+        assert_eq!(&sacch[..45], &orig[..]);
+
+        // 8 errors and 3 erasures.
+        let mut w = orig;
+        for &idx in [0, 5, 10, 15, 20, 25, 30, 35, 40, 41, 44].iter() {
+            w[idx] = Hexbit::new(0o55 ^ w[idx].bits());
+        }
+        assert_eq!(
+            phase2::lcch::decode_with_erasures(&mut w, &[40, 41, 44]),
+            Ok((&orig[..26], 11))
+        );
+
+        let mut buf = [Hexbit::default(); 44];
+        (0..16)
+            .map(|b| Hexbit::new(b * 3))
+            .collect_slice(&mut buf[..]);
+        phase2::ess::encode(&mut buf);
+        let orig = buf;
+
+        // 14 errors.
+        let mut w = orig;
+        for h in w.iter_mut().step_by(3).take(14) {
+            *h = Hexbit::new(0o70 ^ h.bits());
+        }
+        assert_eq!(phase2::ess::decode(&mut w), Ok((&orig[..16], 14)));
     }
 }
@@ -3,7 +3,8 @@
 //!
 //! Encoding is done with a state machine and decoding is done with the Viterbi algorithm,
 //! adapted from *Coding Theory and Cryptography: The Essentials*, Hankerson, Hoffman, et
-//! al, 2000.
+//! al, 2000. The decoder accepts either hard-decision dibits or soft-decision metrics
+//! for each received dibit.
 
 use std;
 use std::ops::{Deref, DerefMut};
@@ -11,6 +12,7 @@ use std::ops::{Deref, DerefMut};
 use collect_slice::CollectSlice;
 
 use crate::bits;
+use crate::error::DecodeError;
 
 use self::Decision::*;
 
@@ -20,10 +22,10 @@ pub type DibitFSM = TrellisFSM<DibitStates>;
 /// 3/4-rate convolutional ("trellis") code state machine.
 pub type TribitFSM = TrellisFSM<TribitStates>;
 
-/// Half-rate convolution ("trellis") code decoder.
+/// Half-rate convolution ("trellis") code decoder, over hard or soft received dibits.
 pub type DibitDecoder<T> = ViterbiDecoder<DibitStates, DibitHistory, DibitWalks, T>;
 
-/// 3/4-rate convolution ("trellis") code decoder.
+/// 3/4-rate convolution ("trellis") code decoder, over hard or soft received dibits.
 pub type TribitDecoder<T> = ViterbiDecoder<TribitStates, TribitHistory, TribitWalks, T>;
 
 pub trait States {
@@ -191,13 +193,13 @@ macro_rules! history_type {
 
         impl Deref for $name {
             type Target = [Option<usize>];
-            fn deref<'a>(&'a self) -> &'a Self::Target {
+            fn deref(&self) -> &Self::Target {
                 &self.0[..]
             }
         }
 
         impl DerefMut for $name {
-            fn deref_mut<'a>(&'a mut self) -> &'a mut Self::Target {
+            fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.0[..]
             }
         }
@@ -226,13 +228,13 @@ macro_rules! impl_walks {
 
         impl Deref for $name {
             type Target = [Walk<$hist>];
-            fn deref<'a>(&'a self) -> &'a Self::Target {
+            fn deref(&self) -> &Self::Target {
                 &self.0[..]
             }
         }
 
         impl DerefMut for $name {
-            fn deref_mut<'a>(&'a mut self) -> &'a mut Self::Target {
+            fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.0[..]
             }
         }
@@ -260,14 +262,76 @@ macro_rules! impl_walks {
 impl_walks!(DibitWalks, DibitHistory, 4);
 impl_walks!(TribitWalks, TribitHistory, 8);
 
+/// Received dibit that can be measured against the ideal dibits on a trellis transition.
+pub trait DibitMetric {
+    /// Compute the branch metric between the received dibit and the given ideal dibit,
+    /// where a smaller metric indicates a more likely dibit.
+    fn distance(&self, ideal: bits::Dibit) -> usize;
+}
+
+/// Hard-decision dibits use the Hamming distance as the branch metric.
+impl DibitMetric for bits::Dibit {
+    fn distance(&self, ideal: bits::Dibit) -> usize {
+        (self.bits() ^ ideal.bits()).count_ones() as usize
+    }
+}
+
+/// Scale applied to soft metrics before they're quantized to integer branch metrics.
+const SOFT_SCALE: f32 = 1024.0;
+
+/// Quantize the given soft metric to an integer branch metric.
+fn quantize(metric: f32) -> usize {
+    (metric * SOFT_SCALE).round() as usize
+}
+
+/// Soft-decision 4-FSK deviation of a received dibit, normalized so the ideal symbols lie
+/// at +3 (01), +1 (00), -1 (10), and -3 (11).
+///
+/// The squared Euclidean distance to the ideal symbol is used as the branch metric.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Deviation(pub f32);
+
+impl DibitMetric for Deviation {
+    fn distance(&self, ideal: bits::Dibit) -> usize {
+        const SYMBOLS: [f32; 4] = [1.0, 3.0, -1.0, -3.0];
+
+        let diff = self.0 - SYMBOLS[ideal.bits() as usize];
+        quantize(diff * diff)
+    }
+}
+
+/// Soft-decision log-likelihood ratios of the high and low bits of a received dibit,
+/// where a positive ratio favors a 0 bit and a negative ratio favors a 1 bit.
+///
+/// The correlation metric is used as the branch metric, in the form of the summed
+/// magnitudes of the ratios that disagree with the ideal bits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DibitLlr(pub f32, pub f32);
+
+impl DibitMetric for DibitLlr {
+    fn distance(&self, ideal: bits::Dibit) -> usize {
+        let bit = |llr: f32, bit: u8| {
+            if (bit == 0) == (llr < 0.0) {
+                quantize(llr.abs())
+            } else {
+                0
+            }
+        };
+
+        bit(self.0, ideal.bits() >> 1) + bit(self.1, ideal.bits() & 1)
+    }
+}
+
 /// Decodes a received convolutional code dibit stream to a nearby codeword using the
-/// truncated Viterbi algorithm.
+/// truncated Viterbi algorithm. The received dibits may be hard `bits::Dibit` decisions
+/// or any soft-decision type implementing `DibitMetric`.
 pub struct ViterbiDecoder<S, H, W, T>
 where
     S: States,
     H: WalkHistory,
     W: Walks<H>,
-    T: Iterator<Item = bits::Dibit>,
+    T: Iterator,
+    T::Item: DibitMetric,
 {
     states: std::marker::PhantomData<S>,
     history: std::marker::PhantomData<H>,
@@ -286,7 +350,8 @@ where
     S: States,
     H: WalkHistory,
     W: Walks<H>,
-    T: Iterator<Item = bits::Dibit>,
+    T: Iterator,
+    T::Item: DibitMetric,
 {
     /// Construct a new `ViterbiDecoder` over the given dibit source.
     pub fn new(src: T) -> ViterbiDecoder<S, H, W, T> {
@@ -317,31 +382,31 @@ where
     }
 
     fn step(&mut self) -> bool {
-        let input = Edge::new(match (self.src.next(), self.src.next()) {
+        let input = match (self.src.next(), self.src.next()) {
             (Some(hi), Some(lo)) => (hi, lo),
             (None, None) => return false,
             _ => panic!("dibits ended on boundary"),
-        });
+        };
 
         self.remain += 1;
         self.switch_walk();
 
         for s in 0..S::size() {
-            let (walk, _) = self.search(s, input);
+            let (walk, _) = self.search(s, &input);
             self.walks[self.cur][s].append(walk);
         }
 
         true
     }
 
-    ///
-    fn search(&self, state: usize, input: Edge) -> (Walk<H>, bool) {
+    /// Find the minimum-distance walk into the given state for the given input edge.
+    fn search(&self, state: usize, input: &(T::Item, T::Item)) -> (Walk<H>, bool) {
         self.walks[self.prev]
             .iter()
             .enumerate()
             .map(|(i, w)| (Edge::new(S::pair(i, state)), w))
             .fold((Walk::default(), false), |(walk, amb), (e, w)| {
-                match w.distance.checked_add(input.distance(e)) {
+                match w.distance.checked_add(e.metric(&input.0, &input.1)) {
                     Some(sum) if sum < walk.distance => (walk.replace(w, sum), false),
                     Some(sum) if sum == walk.distance => (walk.combine(w, sum), true),
                     _ => (walk, amb),
@@ -349,11 +414,11 @@ where
             })
     }
 
-    ///
+    /// Decide the symbol at the current history position from the surviving walks.
     fn decode(&self) -> Decision {
         self.walks[self.cur]
             .iter()
-            .fold(Ambiguous(std::usize::MAX), |s, w| match s {
+            .fold(Ambiguous(usize::MAX), |s, w| match s {
                 Ambiguous(min) | Definite(min, _) if w.distance < min => {
                     Definite(w.distance, w[self.remain])
                 }
@@ -370,9 +435,10 @@ where
     S: States,
     H: WalkHistory,
     W: Walks<H>,
-    T: Iterator<Item = bits::Dibit>,
+    T: Iterator,
+    T::Item: DibitMetric,
 {
-    type Item = Result<S::Symbol, ()>;
+    type Item = Result<S::Symbol, DecodeError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Stop on the symbol before last since the final symbol is always a dummy symbol
@@ -384,12 +450,72 @@ where
         self.remain -= 1;
 
         Some(match self.decode() {
-            Ambiguous(_) | Definite(_, None) => Err(()),
+            Ambiguous(_) | Definite(_, None) => Err(DecodeError::Ambiguous),
             Definite(_, Some(state)) => Ok(S::symbol(state)),
         })
     }
 }
 
+/// Decode the given terminated block of received dibits to the maximum-likelihood
+/// sequence of input symbols using the full Viterbi algorithm with traceback.
+///
+/// The block must contain a dibit pair for each symbol in the given destination followed
+/// by the pair for the flushing symbol from `States::finisher()`. The decoded symbols are
+/// written to the destination and the metric of the decoded path is returned. Among
+/// paths with equal metrics, the first found is chosen.
+pub fn decode_block<S, M>(dibits: &[M], symbols: &mut [S::Symbol]) -> usize
+where
+    S: States,
+    M: DibitMetric,
+{
+    let size = S::size();
+    let steps = symbols.len() + 1;
+
+    assert_eq!(dibits.len(), 2 * steps);
+
+    // Path metric into each state, starting from the initial state.
+    let mut metrics = vec![usize::MAX; size];
+    metrics[0] = 0;
+
+    // Previous state on the surviving path into each state at each step.
+    let mut survivors = vec![0; steps * size];
+
+    for (step, pair) in dibits.chunks(2).enumerate() {
+        let mut next = vec![usize::MAX; size];
+
+        for (state, best) in next.iter_mut().enumerate() {
+            for (prev, &metric) in metrics.iter().enumerate() {
+                let edge = Edge::new(S::pair(prev, state));
+
+                match metric.checked_add(edge.metric(&pair[0], &pair[1])) {
+                    Some(sum) if sum < *best => {
+                        *best = sum;
+                        survivors[step * size + state] = prev;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        metrics = next;
+    }
+
+    // Trace back from the state entered by the flushing symbol, where each state on the
+    // path is the symbol fed in at that step.
+    let last = S::state(S::finisher());
+    let mut state = last;
+
+    for step in (0..steps).rev() {
+        if let Some(sym) = symbols.get_mut(step) {
+            *sym = S::symbol(state);
+        }
+
+        state = survivors[step * size + state];
+    }
+
+    metrics[last]
+}
+
 /// Decoding decision.
 enum Decision {
     Definite(usize, Option<usize>),
@@ -406,7 +532,7 @@ impl<H: WalkHistory> Walk<H> {
     pub fn new(state: usize) -> Walk<H> {
         Walk {
             history: H::default(),
-            distance: if state == 0 { 0 } else { std::usize::MAX },
+            distance: if state == 0 { 0 } else { usize::MAX },
         }
         .init(state)
     }
@@ -456,7 +582,7 @@ impl<H: WalkHistory> DerefMut for Walk<H> {
 
 impl<H: WalkHistory> Default for Walk<H> {
     fn default() -> Self {
-        Walk::new(std::usize::MAX)
+        Walk::new(usize::MAX)
     }
 }
 
@@ -468,8 +594,19 @@ impl Edge {
         Edge(hi.bits() << 2 | lo.bits())
     }
 
-    pub fn distance(&self, other: Edge) -> usize {
-        (self.0 ^ other.0).count_ones() as usize
+    /// Split the edge into its high and low dibits.
+    fn dibits(&self) -> (bits::Dibit, bits::Dibit) {
+        (
+            bits::Dibit::new(self.0 >> 2),
+            bits::Dibit::new(self.0 & 0b11),
+        )
+    }
+
+    /// Compute the branch metric between the given received high and low dibits and
+    /// this edge.
+    pub fn metric<M: DibitMetric>(&self, hi: &M, lo: &M) -> usize {
+        let (ideal_hi, ideal_lo) = self.dibits();
+        hi.distance(ideal_hi) + lo.distance(ideal_lo)
     }
 }
 
@@ -565,17 +702,32 @@ mod test {
     fn test_edge() {
         assert_eq!(
             Edge::new((Dibit::new(0b11), Dibit::new(0b01)))
-                .distance(Edge::new((Dibit::new(0b11), Dibit::new(0b01)))),
+                .metric(&Dibit::new(0b11), &Dibit::new(0b01)),
             0
         );
 
         assert_eq!(
             Edge::new((Dibit::new(0b11), Dibit::new(0b01)))
-                .distance(Edge::new((Dibit::new(0b00), Dibit::new(0b10)))),
+                .metric(&Dibit::new(0b00), &Dibit::new(0b10)),
             4
         );
     }
 
+    #[test]
+    fn test_edge_metric() {
+        assert_eq!(
+            Edge::new((Dibit::new(0b01), Dibit::new(0b10)))
+                .metric(&Deviation(3.0), &Deviation(-1.5)),
+            256
+        );
+
+        assert_eq!(
+            Edge::new((Dibit::new(0b01), Dibit::new(0b10)))
+                .metric(&DibitLlr(2.0, -0.5), &DibitLlr(0.25, 1.0)),
+            256
+        );
+    }
+
     #[test]
     fn test_dibit_decoder() {
         let bits = [1, 2, 2, 2, 2, 1, 3, 3, 0, 2];
@@ -652,4 +804,122 @@ mod test {
         assert_eq!(dec.next().unwrap().unwrap().bits(), 7);
         assert_eq!(dec.next().unwrap().unwrap().bits(), 0);
     }
+
+    /// Encode the given symbols and return the ideal deviation of each output dibit.
+    fn deviations<S: States>(symbols: &[S::Symbol]) -> Vec<f32>
+    where
+        S::Symbol: Copy,
+    {
+        const SYMBOLS: [f32; 4] = [1.0, 3.0, -1.0, -3.0];
+
+        let mut fsm = TrellisFSM::<S>::new();
+        let mut devs = vec![];
+
+        for &s in symbols {
+            let (hi, lo) = fsm.feed(s);
+            devs.push(SYMBOLS[hi.bits() as usize]);
+            devs.push(SYMBOLS[lo.bits() as usize]);
+        }
+
+        let (hi, lo) = fsm.finish();
+        devs.push(SYMBOLS[hi.bits() as usize]);
+        devs.push(SYMBOLS[lo.bits() as usize]);
+
+        devs
+    }
+
+    #[test]
+    fn test_soft_dibit_decoder() {
+        let bits = [1, 2, 2, 2, 2, 1, 3, 3, 0, 2, 1, 0, 3];
+        let mut devs =
+            deviations::<DibitStates>(&bits.iter().map(|&b| Dibit::new(b)).collect::<Vec<_>>());
+
+        // Push a symbol in every other transition just past the nearest decision
+        // boundary, and add some smaller noise everywhere else.
+        for (i, dev) in devs.iter_mut().enumerate() {
+            if i % 4 == 1 {
+                *dev += if *dev > 0.0 { -1.1 } else { 1.1 };
+            } else {
+                *dev += if i % 3 == 0 { 0.4 } else { -0.3 };
+            }
+        }
+
+        let dec = DibitDecoder::new(devs.iter().map(|&d| Deviation(d)));
+        let out: Vec<u8> = dec.map(|d| d.unwrap().bits()).collect();
+        assert_eq!(&out[..], &bits[..]);
+
+        // The high bit is decided by the sign of the deviation and the low bit by its
+        // magnitude.
+        let dec = DibitDecoder::new(devs.iter().map(|&d| DibitLlr(d, 2.0 - d.abs())));
+        let out: Vec<u8> = dec.map(|d| d.unwrap().bits()).collect();
+        assert_eq!(&out[..], &bits[..]);
+    }
+
+    #[test]
+    fn test_soft_tribit_decoder() {
+        let bits = [1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0];
+        let mut devs =
+            deviations::<TribitStates>(&bits.iter().map(|&b| Tribit::new(b)).collect::<Vec<_>>());
+
+        for (i, dev) in devs.iter_mut().enumerate() {
+            if i % 6 == 2 {
+                *dev += if *dev > 0.0 { -1.2 } else { 1.2 };
+            } else {
+                *dev *= 0.8;
+            }
+        }
+
+        let dec = TribitDecoder::new(devs.iter().map(|&d| Deviation(d)));
+        let out: Vec<u8> = dec.map(|d| d.unwrap().bits()).collect();
+        assert_eq!(&out[..], &bits[..]);
+    }
+
+    #[test]
+    fn test_decode_block() {
+        // Confirmed data block of 48 tribits.
+        let tribits: Vec<Tribit> = (0..48).map(|i| Tribit::new((i * 5 + 3) % 8)).collect();
+
+        let mut dibits = vec![];
+        let mut fsm = TribitFSM::new();
+
+        for &tribit in tribits.iter() {
+            let (hi, lo) = fsm.feed(tribit);
+            dibits.push(hi);
+            dibits.push(lo);
+        }
+
+        let (hi, lo) = fsm.finish();
+        dibits.push(hi);
+        dibits.push(lo);
+
+        assert_eq!(dibits.len(), 98);
+
+        let mut out = [Tribit::default(); 48];
+        assert_eq!(decode_block::<TribitStates, _>(&dibits, &mut out), 0);
+        assert_eq!(&out[..], &tribits[..]);
+
+        // Single bit errors spread through the block, including the flushing symbol.
+        for &idx in [0, 13, 40, 71, 97].iter() {
+            dibits[idx] = Dibit::new(dibits[idx].bits() ^ 0b01);
+        }
+
+        let mut out = [Tribit::default(); 48];
+        assert_eq!(decode_block::<TribitStates, _>(&dibits, &mut out), 5);
+        assert_eq!(&out[..], &tribits[..]);
+
+        // Unconfirmed data block of 48 dibits, with soft symbols.
+        let syms: Vec<Dibit> = (0..48).map(|i| Dibit::new((i * 3 + 1) % 4)).collect();
+        let mut devs = deviations::<DibitStates>(&syms);
+
+        for (i, dev) in devs.iter_mut().enumerate() {
+            if i % 5 == 0 {
+                *dev += if *dev > 0.0 { -1.25 } else { 1.25 };
+            }
+        }
+
+        let soft: Vec<Deviation> = devs.iter().map(|&d| Deviation(d)).collect();
+        let mut out = [Dibit::default(); 48];
+        decode_block::<DibitStates, _>(&soft, &mut out);
+        assert_eq!(&out[..], &syms[..]);
+    }
 }
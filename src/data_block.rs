@@ -0,0 +1,147 @@
+//! Trellis coding and interleaving of the 98-dibit data blocks shared by TSBKs and PDUs.
+//!
+//! Half-rate blocks carry 12 bytes and 3/4-rate blocks carry 18 bytes, each coded as one
+//! symbol per dibit pair followed by the flushing symbol.
+
+use crate::bits::{Dibit, DibitBytes, Dibits, Tribit, TribitBytes, Tribits};
+use crate::coding::interleave::p25_data::{self, DIBITS};
+use crate::coding::trellis::{
+    DibitDecoder, DibitMetric, DibitStates, States, TrellisFSM, TribitDecoder, TribitStates,
+};
+use crate::error::DecodeError;
+
+/// Number of bytes carried by a half-rate block.
+pub const HALF_BYTES: usize = 12;
+
+/// Number of bytes carried by a 3/4-rate block.
+pub const THREE_QUARTER_BYTES: usize = 18;
+
+/// Trellis code the given bytes at 1/2 rate and interleave the result.
+pub fn encode_half(bytes: &[u8; HALF_BYTES]) -> [Dibit; DIBITS] {
+    code::<DibitStates, _>(Dibits::new(bytes.iter().cloned()))
+}
+
+/// Trellis code the given bytes at 3/4 rate and interleave the result.
+pub fn encode_three_quarter(bytes: &[u8; THREE_QUARTER_BYTES]) -> [Dibit; DIBITS] {
+    code::<TribitStates, _>(Tribits::new(bytes.iter().cloned()))
+}
+
+/// Deinterleave and decode the given received 1/2-rate block, returning the decoded
+/// bytes and the index of each ambiguous symbol, which is filled with zero.
+pub fn decode_half<M>(dibits: &[M; DIBITS]) -> ([u8; HALF_BYTES], Vec<usize>)
+where
+    M: DibitMetric + Copy,
+{
+    let (symbols, ambiguous) =
+        symbols::<Dibit, _>(DibitDecoder::new(deinterleave(dibits).iter().cloned()));
+
+    let mut bytes = [0; HALF_BYTES];
+
+    for (dest, byte) in bytes.iter_mut().zip(DibitBytes::new(symbols.into_iter())) {
+        *dest = byte;
+    }
+
+    (bytes, ambiguous)
+}
+
+/// Deinterleave and decode the given received 3/4-rate block, returning the decoded
+/// bytes and the index of each ambiguous symbol, which is filled with zero.
+pub fn decode_three_quarter<M>(dibits: &[M; DIBITS]) -> ([u8; THREE_QUARTER_BYTES], Vec<usize>)
+where
+    M: DibitMetric + Copy,
+{
+    let (symbols, ambiguous) =
+        symbols::<Tribit, _>(TribitDecoder::new(deinterleave(dibits).iter().cloned()));
+
+    let mut bytes = [0; THREE_QUARTER_BYTES];
+
+    for (dest, byte) in bytes.iter_mut().zip(TribitBytes::new(symbols.into_iter())) {
+        *dest = byte;
+    }
+
+    (bytes, ambiguous)
+}
+
+/// Trellis code the given symbols, followed by the flushing symbol, and interleave the
+/// result.
+fn code<S: States, T: Iterator<Item = S::Symbol>>(symbols: T) -> [Dibit; DIBITS] {
+    let mut fsm = TrellisFSM::<S>::new();
+    let mut dibits = [Dibit::default(); DIBITS];
+
+    for (dest, sym) in dibits.chunks_mut(2).zip(symbols) {
+        let (hi, lo) = fsm.feed(sym);
+        dest[0] = hi;
+        dest[1] = lo;
+    }
+
+    let (hi, lo) = fsm.finish();
+    dibits[DIBITS - 2] = hi;
+    dibits[DIBITS - 1] = lo;
+
+    p25_data::interleave(&mut dibits);
+
+    dibits
+}
+
+/// Copy and deinterleave the given received block.
+fn deinterleave<M: Copy>(dibits: &[M; DIBITS]) -> [M; DIBITS] {
+    let mut coded = *dibits;
+    p25_data::deinterleave(&mut coded);
+    coded
+}
+
+/// Collect the symbols from the given decoder, filling in ambiguous symbols with the
+/// default symbol and recording their indexes.
+fn symbols<Sym, T>(decoder: T) -> (Vec<Sym>, Vec<usize>)
+where
+    Sym: Default,
+    T: Iterator<Item = Result<Sym, DecodeError>>,
+{
+    let mut ambiguous = Vec::new();
+
+    let symbols = decoder
+        .enumerate()
+        .map(|(i, sym)| {
+            sym.unwrap_or_else(|_| {
+                ambiguous.push(i);
+                Sym::default()
+            })
+        })
+        .collect();
+
+    (symbols, ambiguous)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_half() {
+        let bytes = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x0f, 0xed, 0xcb, 0xa9,
+        ];
+        let mut dibits = encode_half(&bytes);
+
+        assert_eq!(decode_half(&dibits), (bytes, vec![]));
+
+        dibits[7] = Dibit::new(dibits[7].bits() ^ 0b10);
+        dibits[70] = Dibit::new(dibits[70].bits() ^ 0b01);
+        assert_eq!(decode_half(&dibits), (bytes, vec![]));
+    }
+
+    #[test]
+    fn test_three_quarter() {
+        let mut bytes = [0; THREE_QUARTER_BYTES];
+
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (i * 37) as u8;
+        }
+
+        let mut dibits = encode_three_quarter(&bytes);
+        assert_eq!(decode_three_quarter(&dibits), (bytes, vec![]));
+
+        dibits[40] = Dibit::new(dibits[40].bits() ^ 0b01);
+        assert_eq!(decode_three_quarter(&dibits), (bytes, vec![]));
+    }
+}
@@ -0,0 +1,62 @@
+//! Errors that can occur when decoding.
+
+use std;
+
+/// Reason a received word couldn't be decoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The word contained more errors than the code can correct.
+    Uncorrectable,
+    /// More erasures were given than the code can fill.
+    TooManyErasures,
+    /// The degree of the error locator polynomial differed from its number of roots.
+    RootCountMismatch,
+    /// An error location fell outside the (possibly shortened) word.
+    OutOfBounds,
+    /// The decoded data exceeded the width of the shortened code.
+    ShortenedOverflow,
+    /// The trellis decoder couldn't decide between equally likely symbols.
+    Ambiguous,
+    /// The decoded data held a value that isn't defined for its field.
+    InvalidData,
+    /// A symbol held a value outside the code's Galois field.
+    InvalidSymbol,
+    /// Errors were corrected, but a further check showed more errors occurred than could
+    /// be corrected.
+    DetectedUncorrectable,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(match *self {
+            DecodeError::Uncorrectable => "too many errors to correct",
+            DecodeError::TooManyErasures => "too many erasures to correct",
+            DecodeError::RootCountMismatch => "error locator degree differs from root count",
+            DecodeError::OutOfBounds => "error location outside of word",
+            DecodeError::ShortenedOverflow => "decoded data exceeds shortened code width",
+            DecodeError::Ambiguous => "ambiguous trellis decision",
+            DecodeError::InvalidData => "invalid value in decoded data",
+            DecodeError::InvalidSymbol => "symbol outside of field",
+            DecodeError::DetectedUncorrectable => "detected more errors than can be corrected",
+        })
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            DecodeError::OutOfBounds.to_string(),
+            "error location outside of word"
+        );
+        assert_eq!(
+            DecodeError::Ambiguous.to_string(),
+            "ambiguous trellis decision"
+        );
+    }
+}
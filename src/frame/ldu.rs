@@ -0,0 +1,456 @@
+//! Assembly and parsing of the 864-dibit logical link data units (LDU1 and LDU2), which
+//! carry 9 IMBE voice frames along with the LC or ES word and 2 bytes of low-speed data.
+//!
+//! After the frame sync and NID, the voice frames are separated by the 6 40-bit segments
+//! of the LC or ES word and then the 32-bit low-speed data field, which holds 2 bytes
+//! each protected by the (16, 8, 5) cyclic code. A status symbol follows every 35 data
+//! dibits, for 24 in total.
+
+use crate::bits::Dibit;
+use crate::coding::cyclic;
+use crate::error::DecodeError;
+use crate::frame::status::{Status, StatusInserter, StatusStripper, PERIOD};
+use crate::frame::SYNC;
+use crate::imbe;
+use crate::lc_es::{self, WORDS};
+use crate::ldu1::LinkControl;
+use crate::ldu2::EncryptionSync;
+use crate::nid::{Duid, Nid};
+
+/// Number of dibits in an LDU, including status symbols.
+pub const DIBITS: usize = 864;
+
+/// Number of IMBE voice frames in an LDU.
+pub const VOICE_FRAMES: usize = 9;
+
+/// Number of status symbols in an LDU.
+pub const STATUS_SYMBOLS: usize = DIBITS / (PERIOD + 1);
+
+/// Number of bytes of low-speed data in an LDU.
+pub const LSD_BYTES: usize = 2;
+
+/// Number of Hamming words in each segment of the LC or ES field.
+const SEGMENT_WORDS: usize = 4;
+
+/// Number of data dibits in an LDU, excluding status symbols.
+const DATA_DIBITS: usize = DIBITS - STATUS_SYMBOLS;
+
+/// Word carried alongside the voice frames, which also determines the type of LDU.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Word {
+    /// Link control word, carried by the LDU1.
+    Lc(LinkControl),
+    /// Encryption sync word, carried by the LDU2.
+    Es(EncryptionSync),
+}
+
+impl Word {
+    /// Data unit ID of the LDU that carries the word.
+    pub fn duid(&self) -> Duid {
+        match *self {
+            Word::Lc(_) => Duid::Ldu1,
+            Word::Es(_) => Duid::Ldu2,
+        }
+    }
+
+    /// Encode the word into its 24 Hamming words.
+    fn encode(&self) -> [u16; WORDS] {
+        match *self {
+            Word::Lc(ref lc) => lc.encode(),
+            Word::Es(ref es) => es.encode(),
+        }
+    }
+}
+
+/// Logical link data unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ldu {
+    /// 12-bit network access code.
+    pub nac: u16,
+    /// Voice frames, in transmission order.
+    pub voice: [imbe::Frame; VOICE_FRAMES],
+    /// LC or ES word.
+    pub word: Word,
+    /// Low-speed data bytes.
+    pub lsd: [u8; LSD_BYTES],
+}
+
+/// Corrections made to each field while decoding an LDU.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// Number of bits in the received frame sync that differ from the expected sync.
+    pub sync: usize,
+    /// Number of bits corrected in the NID.
+    pub nid: usize,
+    /// Errors corrected in each voice frame, or `None` if the frame couldn't be decoded.
+    pub voice: [Option<imbe::Errors>; VOICE_FRAMES],
+    /// Errors corrected in the LC or ES word, or `None` if it couldn't be decoded.
+    pub word: Option<lc_es::Errors>,
+    /// Number of bits corrected in each low-speed data byte, or `None` if the byte
+    /// couldn't be decoded.
+    pub lsd: [Option<usize>; LSD_BYTES],
+}
+
+/// LDU recovered from a received dibit stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// Decoded fields, where any field that couldn't be decoded is left at its default
+    /// value.
+    pub ldu: Ldu,
+    /// Corrections made to each field.
+    pub report: Report,
+    /// Received status symbols, in transmission order.
+    pub status: [Status; STATUS_SYMBOLS],
+}
+
+impl Ldu {
+    /// Encode the LDU into its 864 transmitted dibits, beginning with the frame sync and
+    /// with the given status in every status symbol.
+    pub fn encode(&self, status: Status) -> [Dibit; DIBITS] {
+        let words = self.word.encode();
+        let nid = Nid::new(self.nac, self.word.duid());
+
+        let mut data = Vec::with_capacity(DATA_DIBITS);
+        push_bits(&mut data, SYNC, 48);
+        push_bits(&mut data, nid.encode(), 64);
+
+        for (i, frame) in self.voice.iter().enumerate() {
+            data.extend_from_slice(&frame.encode());
+
+            match i {
+                1..=6 => {
+                    for &word in segment(&words, i) {
+                        push_bits(&mut data, word as u64, 10);
+                    }
+                }
+                7 => {
+                    for &byte in self.lsd.iter() {
+                        push_bits(&mut data, cyclic::encode(byte) as u64, 16);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq!(data.len(), DATA_DIBITS);
+
+        let mut dibits = [Dibit::default(); DIBITS];
+
+        for (dest, dibit) in dibits
+            .iter_mut()
+            .zip(StatusInserter::new(data.into_iter(), status))
+        {
+            *dest = dibit;
+        }
+
+        dibits
+    }
+
+    /// Try to decode the given 864 received dibits, beginning with the frame sync, into
+    /// an LDU. Each field is decoded independently, so a field that can't be decoded
+    /// doesn't prevent recovering the others.
+    ///
+    /// If the NID was decoded as an LDU1 or LDU2, return `Ok(dec)`, where `dec` holds the
+    /// decoded fields along with the corrections made to each. Otherwise, return an `Err`
+    /// describing the NID error, with `DecodeError::InvalidData` indicating a data unit
+    /// other than an LDU.
+    pub fn decode(dibits: &[Dibit; DIBITS]) -> Result<Decoded, DecodeError> {
+        let mut strip = StatusStripper::new(dibits.iter().cloned());
+        let data = strip.by_ref().collect::<Vec<_>>();
+
+        let mut status = [Status::Unknown; STATUS_SYMBOLS];
+        status.copy_from_slice(strip.status());
+
+        let mut src = data.into_iter();
+
+        let sync = (take_bits(&mut src, 48) ^ SYNC).count_ones() as usize;
+        let (nid, nid_err) = Nid::decode(take_bits(&mut src, 64))?;
+
+        let mut word = match nid.duid {
+            Duid::Ldu1 => Word::Lc(LinkControl::default()),
+            Duid::Ldu2 => Word::Es(EncryptionSync::default()),
+            _ => return Err(DecodeError::InvalidData),
+        };
+
+        let mut voice = [imbe::Frame::default(); VOICE_FRAMES];
+        let mut voice_errs = [None; VOICE_FRAMES];
+        let mut words = [0; WORDS];
+        let mut lsd_words = [0; LSD_BYTES];
+
+        for i in 0..VOICE_FRAMES {
+            let mut buf = [Dibit::default(); imbe::DIBITS];
+
+            for (dest, dibit) in buf.iter_mut().zip(&mut src) {
+                *dest = dibit;
+            }
+
+            if let Ok((frame, errs)) = imbe::Frame::decode(&buf) {
+                voice[i] = frame;
+                voice_errs[i] = Some(errs);
+            }
+
+            match i {
+                1..=6 => {
+                    for dest in words[(i - 1) * SEGMENT_WORDS..i * SEGMENT_WORDS].iter_mut() {
+                        *dest = take_bits(&mut src, 10) as u16;
+                    }
+                }
+                7 => {
+                    for dest in lsd_words.iter_mut() {
+                        *dest = take_bits(&mut src, 16) as u16;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let word_errs = match word {
+            Word::Lc(ref mut lc) => LinkControl::decode(&words).map(|(dec, errs)| {
+                *lc = dec;
+                errs
+            }),
+            Word::Es(ref mut es) => EncryptionSync::decode(&words).map(|(dec, errs)| {
+                *es = dec;
+                errs
+            }),
+        }
+        .ok();
+
+        let mut lsd = [0; LSD_BYTES];
+        let mut lsd_errs = [None; LSD_BYTES];
+
+        for ((dest, err), &w) in lsd.iter_mut().zip(lsd_errs.iter_mut()).zip(&lsd_words) {
+            if let Ok((byte, e)) = cyclic::decode(w) {
+                *dest = byte;
+                *err = Some(e);
+            }
+        }
+
+        Ok(Decoded {
+            ldu: Ldu {
+                nac: nid.nac,
+                voice,
+                word,
+                lsd,
+            },
+            report: Report {
+                sync,
+                nid: nid_err,
+                voice: voice_errs,
+                word: word_errs,
+                lsd: lsd_errs,
+            },
+            status,
+        })
+    }
+}
+
+/// Get the Hamming words of the LC or ES segment that follows the given voice frame.
+fn segment(words: &[u16; WORDS], frame: usize) -> &[u16] {
+    &words[(frame - 1) * SEGMENT_WORDS..frame * SEGMENT_WORDS]
+}
+
+/// Append the given number of low-order bits of the given word as dibits, MSB first.
+fn push_bits(dibits: &mut Vec<Dibit>, word: u64, bits: usize) {
+    dibits.extend(
+        (0..bits / 2)
+            .rev()
+            .map(|i| Dibit::new((word >> (2 * i)) as u8 & 0b11)),
+    );
+}
+
+/// Consume the dibits for the given number of bits from the given source, MSB first.
+fn take_bits<T: Iterator<Item = Dibit>>(src: &mut T, bits: usize) -> u64 {
+    src.take(bits / 2)
+        .fold(0, |word, dibit| word << 2 | dibit.bits() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coding::hamming;
+
+    fn ldu1() -> Ldu {
+        let mut voice = [imbe::Frame::default(); VOICE_FRAMES];
+
+        for (i, frame) in voice.iter_mut().enumerate() {
+            let i = i as u16;
+            *frame = imbe::Frame::new([
+                0xabc ^ i,
+                0x123 + i,
+                0xfed,
+                i << 4,
+                0x7ff - i,
+                0x2aa,
+                0x555,
+                0x5a ^ i,
+            ]);
+        }
+
+        Ldu {
+            nac: 0x293,
+            voice,
+            word: Word::Lc(LinkControl {
+                lcf: 0x00,
+                mfid: 0x00,
+                data: [0x00, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab],
+            }),
+            lsd: [0xa5, 0x3c],
+        }
+    }
+
+    fn clean() -> Report {
+        Report {
+            sync: 0,
+            nid: 0,
+            voice: [Some(imbe::Errors::default()); VOICE_FRAMES],
+            word: Some(lc_es::Errors::default()),
+            lsd: [Some(0); LSD_BYTES],
+        }
+    }
+
+    fn flip(dibits: &mut [Dibit; DIBITS], idx: usize, mask: u8) {
+        dibits[idx] = Dibit::new(dibits[idx].bits() ^ mask);
+    }
+
+    #[test]
+    fn test_layout() {
+        let ldu = ldu1();
+        let dibits = ldu.encode(Status::Idle);
+
+        // Frame sync begins 01 01 01 01 01 11 01 01 ...
+        assert_eq!(dibits[0], Dibit::new(0b01));
+        assert_eq!(dibits[5], Dibit::new(0b11));
+        assert_eq!(take_bits(&mut dibits[..24].iter().cloned(), 48), SYNC);
+
+        // The NID is split by the first status symbol.
+        let nid = dibits[24..35].iter().chain(dibits[36..57].iter()).cloned();
+        assert_eq!(
+            take_bits(&mut nid.into_iter(), 64),
+            Nid::new(0x293, Duid::Ldu1).encode()
+        );
+
+        for i in 0..STATUS_SYMBOLS {
+            assert_eq!(dibits[i * 36 + 35], Dibit::new(0b11));
+        }
+
+        // The first voice frame starts right after the NID.
+        assert_eq!(&dibits[57..71], &ldu.voice[0].encode()[..14]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let ldu = ldu1();
+        let dec = Ldu::decode(&ldu.encode(Status::Busy)).unwrap();
+
+        assert_eq!(dec.ldu, ldu);
+        assert_eq!(dec.report, clean());
+        assert_eq!(dec.status, [Status::Busy; STATUS_SYMBOLS]);
+
+        let ldu = Ldu {
+            word: Word::Es(EncryptionSync {
+                mi: [0xde, 0xad, 0xbe, 0xef, 0x01, 0x23, 0x45, 0x67, 0x89],
+                algid: 0xaa,
+                kid: 0x55a5,
+            }),
+            ..ldu1()
+        };
+        let dibits = ldu.encode(Status::Talkaround);
+        assert_eq!(dibits[35], Dibit::new(0b00));
+
+        let dec = Ldu::decode(&dibits).unwrap();
+        assert_eq!(dec.ldu, ldu);
+        assert_eq!(dec.ldu.word.duid(), Duid::Ldu2);
+        assert_eq!(dec.report, clean());
+    }
+
+    #[test]
+    fn test_errors() {
+        let ldu = ldu1();
+        let mut dibits = ldu.encode(Status::Idle);
+
+        // Sync, NID, and the first bit of the first voice frame.
+        flip(&mut dibits, 3, 0b01);
+        flip(&mut dibits, 30, 0b11);
+        flip(&mut dibits, 57, 0b10);
+
+        // The status symbols are passed through as received.
+        flip(&mut dibits, 71, 0b01);
+
+        let dec = Ldu::decode(&dibits).unwrap();
+        assert_eq!(dec.ldu, ldu);
+        assert_eq!(dec.report.sync, 1);
+        assert_eq!(dec.report.nid, 2);
+        assert_eq!(dec.report.voice[0].unwrap().e0(), 1);
+        assert_eq!(dec.status[1], Status::Unknown);
+        assert_eq!(dec.status[2], Status::Idle);
+
+        // Wipe out the first LSD byte, which starts after 8 voice frames and the LC
+        // segments, and overwhelm the second voice frame.
+        let lsd_start = 56 + 8 * 72 + 6 * 20;
+        let mut dibits = ldu.encode(Status::Idle);
+
+        let data_idx = |i: usize| i / PERIOD * (PERIOD + 1) + i % PERIOD;
+
+        for i in 0..6 {
+            flip(&mut dibits, data_idx(lsd_start + i), 0b11);
+            flip(&mut dibits, data_idx(56 + 72 + i * 6), 0b11);
+        }
+
+        let dec = Ldu::decode(&dibits).unwrap();
+        assert_eq!(dec.report.voice[0], Some(imbe::Errors::default()));
+        assert!(dec.report.voice[1].unwrap().total() > 0);
+        assert_ne!(dec.ldu.voice[1], ldu.voice[1]);
+        assert_eq!(dec.report.lsd[0], None);
+        assert_eq!(dec.report.lsd[1], Some(0));
+        assert_eq!(dec.ldu.lsd, [0, 0x3c]);
+        assert_eq!(dec.ldu.word, ldu.word);
+        assert_eq!(dec.ldu.voice[2..], ldu.voice[2..]);
+    }
+
+    #[test]
+    fn test_word() {
+        let ldu = ldu1();
+        let mut dibits = ldu.encode(Status::Idle);
+
+        let bad = (0..1 << 10)
+            .find(|&w| hamming::shortened::decode(w).is_err())
+            .unwrap();
+
+        // Replace 13 LC Hamming words, more than the Reed-Solomon code can fill.
+        let data_idx = |i: usize| i / PERIOD * (PERIOD + 1) + i % PERIOD;
+
+        for seg in 0..4 {
+            let start = 56 + (seg + 2) * 72 + seg * 20;
+
+            for w in 0..4 {
+                for (k, d) in (0..5).rev().enumerate() {
+                    dibits[data_idx(start + w * 5 + k)] = Dibit::new((bad >> (2 * d)) as u8 & 3);
+                }
+            }
+        }
+
+        let dec = Ldu::decode(&dibits).unwrap();
+        assert_eq!(dec.report.word, None);
+        assert_eq!(dec.ldu.word, Word::Lc(LinkControl::default()));
+        assert_eq!(dec.ldu.voice, ldu.voice);
+        assert_eq!(dec.ldu.lsd, ldu.lsd);
+    }
+
+    #[test]
+    fn test_nid() {
+        let mut dibits = ldu1().encode(Status::Idle);
+
+        // Replace the NID with that of a TSBK.
+        let mut data = Vec::new();
+        push_bits(&mut data, Nid::new(0x293, Duid::Tsbk).encode(), 64);
+        dibits[24..35].copy_from_slice(&data[..11]);
+        dibits[36..57].copy_from_slice(&data[11..]);
+        assert_eq!(Ldu::decode(&dibits), Err(DecodeError::InvalidData));
+
+        for i in 24..35 {
+            flip(&mut dibits, i, 0b11);
+        }
+        assert!(Ldu::decode(&dibits).is_err());
+    }
+}
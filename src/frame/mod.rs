@@ -0,0 +1,16 @@
+//! Assembly and parsing of complete P25 Phase 1 data units in their over-the-air dibit
+//! layout, which ties together the field codecs with the frame sync, NID, and status
+//! symbols.
+
+pub mod ldu;
+pub mod status;
+pub mod sync;
+
+/// The 48-bit frame sync that begins every data unit.
+pub const SYNC: u64 = 0x5575_f5ff_77ff;
+
+/// Number of dibits in the frame sync.
+pub const SYNC_DIBITS: usize = 24;
+
+/// Number of dibits in the NID word.
+pub const NID_DIBITS: usize = 32;
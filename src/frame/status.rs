@@ -0,0 +1,250 @@
+//! Status symbols, which are inserted into the transmitted dibit stream after every 35
+//! data dibits, counting from the start of the frame sync.
+//!
+//! Status symbols aren't covered by any data unit's coding, so they must be removed from
+//! a received stream before it's passed to a decoder such as `trellis::DibitDecoder` or
+//! `bits::DibitBytes`.
+
+use crate::bits::Dibit;
+
+/// Number of data dibits between status symbols.
+pub const PERIOD: usize = 35;
+
+/// Value of a status symbol.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Unknown channel state, used for talkaround.
+    Talkaround,
+    /// Inbound channel is busy.
+    Busy,
+    /// Unknown channel state, used for inbound or outbound traffic.
+    Unknown,
+    /// Inbound channel is idle.
+    Idle,
+}
+
+impl Status {
+    /// Parse the given received status dibit.
+    pub fn from_dibit(dibit: Dibit) -> Status {
+        use self::Status::*;
+
+        match dibit.bits() {
+            0b00 => Talkaround,
+            0b01 => Busy,
+            0b10 => Unknown,
+            0b11 => Idle,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Convert the status to its transmitted dibit.
+    pub fn to_dibit(self) -> Dibit {
+        use self::Status::*;
+
+        Dibit::new(match self {
+            Talkaround => 0b00,
+            Busy => 0b01,
+            Unknown => 0b10,
+            Idle => 0b11,
+        })
+    }
+}
+
+/// Iterator that inserts a status symbol after every 35 dibits from a source of data
+/// dibits, counted from the first dibit of the frame sync.
+pub struct StatusInserter<T: Iterator<Item = Dibit>> {
+    /// Source of data dibits.
+    src: T,
+    /// Status to transmit in each status symbol.
+    status: Status,
+    /// Number of data dibits yielded since the last status symbol.
+    pos: usize,
+}
+
+impl<T: Iterator<Item = Dibit>> StatusInserter<T> {
+    /// Construct a new `StatusInserter` that inserts the given status into the data
+    /// dibits from the given source, where the first source dibit is the first dibit of
+    /// the frame sync.
+    pub fn new(src: T, status: Status) -> StatusInserter<T> {
+        StatusInserter::with_offset(src, status, 0)
+    }
+
+    /// Construct a new `StatusInserter` that inserts the given status into the data
+    /// dibits from the given source, where the first yielded dibit comes the given number
+    /// of transmitted dibits, including status symbols, after the first dibit of the
+    /// frame sync.
+    pub fn with_offset(src: T, status: Status, offset: usize) -> StatusInserter<T> {
+        StatusInserter {
+            src,
+            status,
+            pos: offset % (PERIOD + 1),
+        }
+    }
+
+    /// Change the status transmitted in following status symbols.
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+    }
+}
+
+impl<T: Iterator<Item = Dibit>> Iterator for StatusInserter<T> {
+    type Item = Dibit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A status symbol follows every full period, even at the end of the source.
+        if self.pos == PERIOD {
+            self.pos = 0;
+            return Some(self.status.to_dibit());
+        }
+
+        let next = self.src.next()?;
+        self.pos += 1;
+
+        Some(next)
+    }
+}
+
+/// Iterator that removes the status symbol following every 35 data dibits from a source
+/// of received dibits, yielding only the data dibits and recording each status.
+///
+/// Each status symbol is consumed when the data dibit following it is requested, or when
+/// the source ends.
+pub struct StatusStripper<T: Iterator<Item = Dibit>> {
+    /// Source of received dibits.
+    src: T,
+    /// Position of the next source dibit within the current period of data dibits and
+    /// status symbol.
+    pos: usize,
+    /// Status symbols removed so far.
+    status: Vec<Status>,
+}
+
+impl<T: Iterator<Item = Dibit>> StatusStripper<T> {
+    /// Construct a new `StatusStripper` over the given source, where the first source
+    /// dibit is the first dibit of the frame sync.
+    pub fn new(src: T) -> StatusStripper<T> {
+        StatusStripper::with_offset(src, 0)
+    }
+
+    /// Construct a new `StatusStripper` over the given source, where the first source
+    /// dibit comes the given number of received dibits, including status symbols, after
+    /// the first dibit of the frame sync.
+    pub fn with_offset(src: T, offset: usize) -> StatusStripper<T> {
+        StatusStripper {
+            src,
+            pos: offset % (PERIOD + 1),
+            status: Vec::new(),
+        }
+    }
+
+    /// Status symbols removed so far, in received order.
+    pub fn status(&self) -> &[Status] {
+        &self.status[..]
+    }
+
+    /// Take the status symbols removed so far, leaving none recorded.
+    pub fn take_status(&mut self) -> Vec<Status> {
+        std::mem::take(&mut self.status)
+    }
+}
+
+impl<T: Iterator<Item = Dibit>> Iterator for StatusStripper<T> {
+    type Item = Dibit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == PERIOD {
+            let status = self.src.next()?;
+            self.status.push(Status::from_dibit(status));
+            self.pos = 0;
+        }
+
+        let next = self.src.next()?;
+        self.pos += 1;
+
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data(len: usize) -> Vec<Dibit> {
+        (0..len).map(|i| Dibit::new(i as u8 % 3)).collect()
+    }
+
+    #[test]
+    fn test_status() {
+        for bits in 0..4 {
+            assert_eq!(Status::from_dibit(Dibit::new(bits)).to_dibit().bits(), bits);
+        }
+
+        assert_eq!(Status::from_dibit(Dibit::new(0b01)), Status::Busy);
+        assert_eq!(Status::Idle.to_dibit(), Dibit::new(0b11));
+    }
+
+    #[test]
+    fn test_insert() {
+        let src = data(80);
+        let out = StatusInserter::new(src.iter().cloned(), Status::Idle).collect::<Vec<_>>();
+
+        assert_eq!(out.len(), 82);
+        assert_eq!(&out[..35], &src[..35]);
+        assert_eq!(out[35], Dibit::new(0b11));
+        assert_eq!(&out[36..71], &src[35..70]);
+        assert_eq!(out[71], Dibit::new(0b11));
+        assert_eq!(&out[72..], &src[70..]);
+
+        // A full final period is followed by its status symbol.
+        let out = StatusInserter::new(data(70).into_iter(), Status::Busy).collect::<Vec<_>>();
+        assert_eq!(out.len(), 72);
+        assert_eq!(out[71], Dibit::new(0b01));
+
+        let mut ins = StatusInserter::new(data(70).into_iter(), Status::Busy);
+        let first = (&mut ins).take(36).collect::<Vec<_>>();
+        assert_eq!(first[35], Dibit::new(0b01));
+        ins.set_status(Status::Talkaround);
+        assert_eq!(ins.last(), Some(Dibit::new(0b00)));
+
+        // Starting mid-frame, such as after the frame sync and NID.
+        let full = StatusInserter::new(src.iter().cloned(), Status::Idle).collect::<Vec<_>>();
+        let out = StatusInserter::with_offset(src[56..].iter().cloned(), Status::Idle, 57)
+            .collect::<Vec<_>>();
+        assert_eq!(out, &full[57..]);
+        assert_eq!(out[71 - 57], Dibit::new(0b11));
+
+        // Starting just before a status symbol.
+        let mut ins = StatusInserter::with_offset(src.iter().cloned(), Status::Busy, 35 + 36);
+        assert_eq!(ins.next(), Some(Dibit::new(0b01)));
+        assert_eq!(ins.next(), Some(src[0]));
+    }
+
+    #[test]
+    fn test_strip() {
+        let src = data(100);
+        let mut tx = StatusInserter::new(src.iter().cloned(), Status::Idle).collect::<Vec<_>>();
+        tx[71] = Status::Unknown.to_dibit();
+
+        let mut strip = StatusStripper::new(tx.iter().cloned());
+        assert_eq!((&mut strip).take(35).collect::<Vec<_>>(), &src[..35]);
+        assert!(strip.status().is_empty());
+
+        assert_eq!(strip.next(), Some(src[35]));
+        assert_eq!(strip.status(), &[Status::Idle]);
+
+        assert_eq!(strip.by_ref().collect::<Vec<_>>(), &src[36..]);
+        assert_eq!(strip.take_status(), vec![Status::Idle, Status::Unknown]);
+        assert!(strip.status().is_empty());
+
+        // Starting mid-frame, such as after the frame sync and NID.
+        let mut strip = StatusStripper::with_offset(tx[57..].iter().cloned(), 57);
+        assert_eq!(strip.by_ref().collect::<Vec<_>>(), &src[56..]);
+        assert_eq!(strip.status(), &[Status::Unknown]);
+
+        // The final status symbol is consumed at the end of the source.
+        let tx = StatusInserter::new(data(70).into_iter(), Status::Idle).collect::<Vec<_>>();
+        let mut strip = StatusStripper::new(tx.into_iter());
+        assert_eq!(strip.by_ref().count(), 70);
+        assert_eq!(strip.status(), &[Status::Idle, Status::Idle]);
+    }
+}
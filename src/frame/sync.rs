@@ -0,0 +1,374 @@
+//! Detection of the frame sync in a received dibit stream, followed by decoding of the
+//! NID that comes after it.
+//!
+//! Each received dibit is shifted into a 48-bit register, which is compared against the
+//! frame sync and its polarity-inverted form. A receiver with inverted polarity swaps the
+//! positive and negative C4FM deviations, which flips the MSB of every dibit.
+//!
+//! A frame sync received a few dibits early or late can still fall within the tolerance,
+//! since its overlap with the expected sync is only 7 to 10 bits away for small shifts. So
+//! after a frame sync is first found, the register keeps being checked for a closer match
+//! before the detector locks onto the best alignment.
+
+use crate::bits::Dibit;
+use crate::error::DecodeError;
+use crate::frame::status::PERIOD;
+use crate::frame::{NID_DIBITS, SYNC, SYNC_DIBITS};
+use crate::nid::Nid;
+
+/// Mask that flips the MSB of each dibit in a 48-bit word.
+const INVERT: u64 = 0xaaaa_aaaa_aaaa;
+
+/// Mask covering the bits of the frame sync.
+const SYNC_MASK: u64 = (1 << 48) - 1;
+
+/// Number of dibits after a frame sync is found to keep looking for a closer match. Any
+/// better-aligned frame sync overlaps the first, and the dibits received in the meantime
+/// are still held in the register once the detector locks on.
+const LOOKAHEAD: usize = SYNC_DIBITS - 1;
+
+/// Polarity of the received symbols.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// Symbols are received as transmitted.
+    Normal,
+    /// Positive and negative deviations are swapped.
+    Inverted,
+}
+
+impl Polarity {
+    /// Correct the given received dibit for the polarity.
+    pub fn correct(self, dibit: Dibit) -> Dibit {
+        match self {
+            Polarity::Normal => dibit,
+            Polarity::Inverted => Dibit::new(dibit.bits() ^ 0b10),
+        }
+    }
+}
+
+/// Frame sync found in a dibit stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SyncMatch {
+    /// Index in the stream of the first dibit of the frame sync.
+    pub offset: usize,
+    /// Polarity of the received frame sync.
+    pub polarity: Polarity,
+    /// Number of bits in the received frame sync that differ from the expected sync.
+    pub errors: usize,
+}
+
+/// Frame sync and NID found in a dibit stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Detection {
+    /// Location and quality of the frame sync.
+    pub sync: SyncMatch,
+    /// Result of decoding the NID that follows the frame sync, as returned by
+    /// `Nid::decode`.
+    pub nid: Result<(Nid, usize), DecodeError>,
+}
+
+/// Current step of the detector.
+#[derive(Copy, Clone, Debug)]
+enum State {
+    /// Searching for the frame sync.
+    Search,
+    /// Watching for a frame sync closer than the one found.
+    Candidate {
+        /// Closest frame sync so far.
+        sync: SyncMatch,
+        /// Number of dibits received after the frame sync.
+        count: usize,
+    },
+    /// Collecting the NID following a frame sync.
+    Nid {
+        /// Frame sync that started the frame.
+        sync: SyncMatch,
+        /// Received NID bits so far.
+        word: u64,
+        /// Number of dibits received after the frame sync.
+        count: usize,
+    },
+}
+
+/// Streaming frame sync detector, which yields the location of each frame sync within
+/// a tolerance of bit errors along with the decoded NID.
+#[derive(Copy, Clone, Debug)]
+pub struct SyncDetector {
+    /// Maximum number of bit errors allowed in a frame sync.
+    tolerance: usize,
+    /// Most recently received 48 bits.
+    reg: u64,
+    /// Number of dibits received so far.
+    pos: usize,
+    /// Current step.
+    state: State,
+}
+
+impl SyncDetector {
+    /// Construct a new `SyncDetector` that accepts frame syncs with up to the given
+    /// number of bit errors.
+    pub fn new(tolerance: usize) -> SyncDetector {
+        assert!(tolerance < 24);
+
+        SyncDetector {
+            tolerance,
+            reg: 0,
+            pos: 0,
+            state: State::Search,
+        }
+    }
+
+    /// Feed in the next received dibit.
+    ///
+    /// Once a frame sync has been found and the following NID received, return
+    /// `Some(det)`, where `det` locates the frame sync and holds the NID decoding result.
+    /// Of overlapping frame syncs within the tolerance, the one with the fewest errors is
+    /// taken. The status symbol within the NID is skipped, and no new frame sync is
+    /// searched for until the NID is complete. Otherwise, return `None`.
+    pub fn feed(&mut self, dibit: Dibit) -> Option<Detection> {
+        self.reg = (self.reg << 2 | dibit.bits() as u64) & SYNC_MASK;
+        self.pos += 1;
+
+        match self.state {
+            State::Search => {
+                if let Some(sync) = self.check() {
+                    self.state = State::Candidate { sync, count: 0 };
+                }
+
+                None
+            }
+            State::Candidate { sync, count } => match self.check() {
+                Some(closer) if closer.errors < sync.errors => {
+                    self.state = State::Candidate {
+                        sync: closer,
+                        count: 0,
+                    };
+
+                    None
+                }
+                _ if count + 1 < LOOKAHEAD => {
+                    self.state = State::Candidate {
+                        sync,
+                        count: count + 1,
+                    };
+
+                    None
+                }
+                _ => self.lock(sync, count + 1),
+            },
+            State::Nid { .. } => self.collect(dibit),
+        }
+    }
+
+    /// Start collecting the NID after the given frame sync, replaying the given number of
+    /// most recent dibits, which were received after it.
+    fn lock(&mut self, sync: SyncMatch, count: usize) -> Option<Detection> {
+        self.state = State::Nid {
+            sync,
+            word: 0,
+            count: 0,
+        };
+
+        (0..count).rev().find_map(|i| {
+            let dibit = Dibit::new((self.reg >> (2 * i)) as u8 & 0b11);
+            self.collect(dibit)
+        })
+    }
+
+    /// Add the given dibit to the NID being collected.
+    fn collect(&mut self, dibit: Dibit) -> Option<Detection> {
+        let (sync, mut word, mut count) = match self.state {
+            State::Nid { sync, word, count } => (sync, word, count),
+            _ => unreachable!(),
+        };
+
+        // The first status symbol falls within the NID.
+        if SYNC_DIBITS + count != PERIOD {
+            word = word << 2 | sync.polarity.correct(dibit).bits() as u64;
+        }
+
+        count += 1;
+
+        if count < NID_DIBITS + 1 {
+            self.state = State::Nid { sync, word, count };
+            return None;
+        }
+
+        self.state = State::Search;
+
+        Some(Detection {
+            sync,
+            nid: Nid::decode(word),
+        })
+    }
+
+    /// Check if the register holds a frame sync within the tolerance.
+    fn check(&self) -> Option<SyncMatch> {
+        if self.pos < SYNC_DIBITS {
+            return None;
+        }
+
+        let normal = (self.reg ^ SYNC).count_ones() as usize;
+        let inverted = (self.reg ^ SYNC ^ INVERT).count_ones() as usize;
+
+        let (polarity, errors) = if normal <= inverted {
+            (Polarity::Normal, normal)
+        } else {
+            (Polarity::Inverted, inverted)
+        };
+
+        if errors > self.tolerance {
+            return None;
+        }
+
+        Some(SyncMatch {
+            offset: self.pos - SYNC_DIBITS,
+            polarity,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::ldu::{Ldu, Word};
+    use crate::frame::status::Status;
+    use crate::imbe;
+    use crate::ldu1::LinkControl;
+    use crate::nid::Duid;
+
+    fn ldu() -> [Dibit; 864] {
+        Ldu {
+            nac: 0x293,
+            voice: [imbe::Frame::default(); 9],
+            word: Word::Lc(LinkControl::default()),
+            lsd: [0x12, 0x34],
+        }
+        .encode(Status::Idle)
+    }
+
+    fn detect(det: &mut SyncDetector, dibits: &[Dibit]) -> Vec<Detection> {
+        dibits.iter().filter_map(|&d| det.feed(d)).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        let frame = ldu();
+
+        let mut stream = vec![Dibit::new(0b10); 17];
+        stream.extend_from_slice(&frame);
+        stream.extend_from_slice(&frame);
+
+        let mut det = SyncDetector::new(0);
+        let found = detect(&mut det, &stream);
+
+        assert_eq!(found.len(), 2);
+
+        for (d, &offset) in found.iter().zip([17, 17 + 864].iter()) {
+            assert_eq!(
+                d.sync,
+                SyncMatch {
+                    offset,
+                    polarity: Polarity::Normal,
+                    errors: 0,
+                }
+            );
+            assert_eq!(d.nid, Ok((Nid::new(0x293, Duid::Ldu1), 0)));
+        }
+
+        // Too short for the NID.
+        let mut det = SyncDetector::new(0);
+        assert!(detect(&mut det, &frame[..56]).is_empty());
+        assert!(det.feed(frame[56]).is_some());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut frame = ldu();
+
+        for &i in [2, 9, 20].iter() {
+            frame[i] = Dibit::new(frame[i].bits() ^ 0b01);
+        }
+
+        // Errors in the NID, including the status symbol, which is skipped.
+        frame[30] = Dibit::new(frame[30].bits() ^ 0b11);
+        frame[35] = Dibit::new(frame[35].bits() ^ 0b11);
+
+        assert!(detect(&mut SyncDetector::new(2), &frame).is_empty());
+
+        let found = detect(&mut SyncDetector::new(3), &frame);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].sync.offset, 0);
+        assert_eq!(found[0].sync.errors, 3);
+        assert_eq!(found[0].nid, Ok((Nid::new(0x293, Duid::Ldu1), 2)));
+    }
+
+    #[test]
+    fn test_inverted() {
+        let frame = ldu()
+            .iter()
+            .map(|&d| Polarity::Inverted.correct(d))
+            .collect::<Vec<_>>();
+
+        let mut stream = vec![Dibit::new(0b00); 5];
+        stream.extend_from_slice(&frame);
+
+        let found = detect(&mut SyncDetector::new(4), &stream);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].sync,
+            SyncMatch {
+                offset: 5,
+                polarity: Polarity::Inverted,
+                errors: 0,
+            }
+        );
+        assert_eq!(found[0].nid, Ok((Nid::new(0x293, Duid::Ldu1), 0)));
+
+        assert_eq!(
+            Polarity::Inverted.correct(Dibit::new(0b01)),
+            Dibit::new(0b11)
+        );
+        assert_eq!(Polarity::Normal.correct(Dibit::new(0b01)), Dibit::new(0b01));
+    }
+
+    #[test]
+    fn test_preamble() {
+        let mut frame = ldu();
+
+        for &i in [4, 15].iter() {
+            frame[i] = Dibit::new(frame[i].bits() ^ 0b10);
+        }
+
+        // The frame sync starts with 01 dibits, so when preceded by more of them, several
+        // earlier alignments fall within the tolerance.
+        let mut stream = vec![Dibit::new(0b01); 30];
+        stream.extend_from_slice(&frame);
+
+        let found = detect(&mut SyncDetector::new(8), &stream);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].sync,
+            SyncMatch {
+                offset: 30,
+                polarity: Polarity::Normal,
+                errors: 2,
+            }
+        );
+        assert_eq!(found[0].nid, Ok((Nid::new(0x293, Duid::Ldu1), 0)));
+    }
+
+    #[test]
+    fn test_nid_error() {
+        let mut frame = ldu();
+
+        for d in frame[24..35].iter_mut() {
+            *d = Dibit::new(d.bits() ^ 0b11);
+        }
+
+        let found = detect(&mut SyncDetector::new(0), &frame);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].nid.is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Encoding and decoding of the P25 header data unit (HDU), which carries the encryption
+//! and talkgroup parameters of a voice call protected by the (36, 20, 17) Reed-Solomon
+//! code, with each hexbit further protected by the (18, 6, 8) shortened Golay code.
+
+use crate::bits::{Hexbit, HexbitBytes, Hexbits};
+use crate::coding::{golay, reed_solomon};
+use crate::error::DecodeError;
+
+/// Number of Golay-coded words in the HDU.
+pub const WORDS: usize = 36;
+
+/// Header carried by the HDU.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Header {
+    /// 72-bit message indicator, the initialization vector for encryption.
+    pub mi: [u8; 9],
+    /// Manufacturer ID.
+    pub mfid: u8,
+    /// Encryption algorithm ID.
+    pub algid: u8,
+    /// Encryption key ID.
+    pub kid: u16,
+    /// Talkgroup ID.
+    pub tgid: u16,
+}
+
+/// Errors corrected at each coding layer while decoding an HDU.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Errors {
+    /// Number of bits corrected by the Golay code across all words.
+    pub golay: usize,
+    /// Number of Golay words that couldn't be decoded and were passed to the
+    /// Reed-Solomon decoder as erasures.
+    pub erased: usize,
+    /// Number of hexbits corrected by the Reed-Solomon code, including filled erasures.
+    pub rs: usize,
+}
+
+impl Header {
+    /// Encode the header into the 36 18-bit Golay codewords of the HDU, in transmission
+    /// order, for 648 bits in total.
+    pub fn encode(&self) -> [u32; WORDS] {
+        let bytes = self.to_bytes();
+        let mut buf = [Hexbit::default(); WORDS];
+
+        for (dest, hexbit) in buf.iter_mut().zip(Hexbits::new(bytes.iter().cloned())) {
+            *dest = hexbit;
+        }
+
+        reed_solomon::long::encode(&mut buf);
+
+        let mut words = [0; WORDS];
+
+        for (word, hexbit) in words.iter_mut().zip(buf.iter()) {
+            *word = golay::shortened::encode(hexbit.bits());
+        }
+
+        words
+    }
+
+    /// Try to decode the given 36 18-bit words to the nearest header. Words the Golay
+    /// code can't correct are treated as erasures by the Reed-Solomon code, so any
+    /// combination of e hexbit errors and f failed words with 2e + f ≤ 16 can be
+    /// corrected.
+    ///
+    /// If decoding was successful, return `Ok((header, errs))`, where `header` is the
+    /// decoded header and `errs` counts the errors corrected at each layer. Otherwise,
+    /// return an `Err` describing the unrecoverable error.
+    pub fn decode(words: &[u32; WORDS]) -> Result<(Header, Errors), DecodeError> {
+        let mut buf = [Hexbit::default(); WORDS];
+        let mut erasures = Vec::new();
+        let mut errs = Errors::default();
+
+        for (i, (dest, &word)) in buf.iter_mut().zip(words.iter()).enumerate() {
+            match golay::shortened::decode(word) {
+                Ok((data, err)) => {
+                    *dest = Hexbit::new(data);
+                    errs.golay += err;
+                }
+                Err(_) => erasures.push(i),
+            }
+        }
+
+        errs.erased = erasures.len();
+
+        let (data, err) = reed_solomon::long::decode_with_erasures(&mut buf, &erasures)?;
+        errs.rs = err;
+
+        let mut bytes = [0; 15];
+
+        for (dest, byte) in bytes.iter_mut().zip(HexbitBytes::new(data.iter().cloned())) {
+            *dest = byte;
+        }
+
+        Ok((Header::from_bytes(&bytes), errs))
+    }
+
+    /// Pack the header into its 120-bit transmitted form.
+    fn to_bytes(self) -> [u8; 15] {
+        let mut bytes = [0; 15];
+
+        bytes[..9].copy_from_slice(&self.mi);
+        bytes[9] = self.mfid;
+        bytes[10] = self.algid;
+        bytes[11] = (self.kid >> 8) as u8;
+        bytes[12] = self.kid as u8;
+        bytes[13] = (self.tgid >> 8) as u8;
+        bytes[14] = self.tgid as u8;
+
+        bytes
+    }
+
+    /// Unpack a header from its 120-bit transmitted form.
+    fn from_bytes(bytes: &[u8; 15]) -> Header {
+        let mut mi = [0; 9];
+        mi.copy_from_slice(&bytes[..9]);
+
+        Header {
+            mi,
+            mfid: bytes[9],
+            algid: bytes[10],
+            kid: (bytes[11] as u16) << 8 | bytes[12] as u16,
+            tgid: (bytes[13] as u16) << 8 | bytes[14] as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn header() -> Header {
+        Header {
+            mi: [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x11],
+            mfid: 0x90,
+            algid: 0x84,
+            kid: 0x1234,
+            tgid: 0xbeef,
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let words = Header::default().encode();
+        assert!(words.iter().all(|&w| w == 0));
+
+        let h = header();
+        let words = h.encode();
+        assert!(words.iter().all(|&w| w >> 18 == 0));
+
+        // The first hexbits carry the MI, MSB first.
+        assert_eq!(words[0], golay::shortened::encode(0x12 >> 2));
+        assert_eq!(
+            words[1],
+            golay::shortened::encode((0x12 & 0b11) << 4 | 0x34 >> 4)
+        );
+        assert_eq!(words[19], golay::shortened::encode(0xef & 0b111111));
+
+        assert_eq!(Header::decode(&words), Ok((h, Errors::default())));
+    }
+
+    #[test]
+    fn test_decode() {
+        let h = header();
+        let words = h.encode();
+
+        // Errors within the Golay correction radius.
+        let mut recv = words;
+        recv[0] ^= 0b111;
+        recv[20] ^= 1 << 17;
+        recv[35] ^= 0b101 << 9;
+        assert_eq!(
+            Header::decode(&recv),
+            Ok((
+                h,
+                Errors {
+                    golay: 6,
+                    erased: 0,
+                    rs: 0
+                }
+            ))
+        );
+
+        // Words replaced by other codewords pass the Golay layer untouched.
+        let mut recv = words;
+        for i in 0..8 {
+            recv[i * 4] = golay::shortened::encode(0b111111);
+        }
+        let (dec, errs) = Header::decode(&recv).unwrap();
+        assert_eq!(dec, h);
+        assert_eq!(errs.golay, 0);
+        assert_eq!(errs.erased, 0);
+        assert!(errs.rs > 0 && errs.rs <= 8);
+
+        // Failed Golay words become erasures.
+        let mut recv = words;
+        for i in 0..16 {
+            recv[i * 2] ^= 0b1111;
+        }
+        let (dec, errs) = Header::decode(&recv).unwrap();
+        assert_eq!(dec, h);
+        assert_eq!(errs.erased, 16);
+        assert!(errs.rs <= 16);
+
+        // Mixed errors and erasures at the limit.
+        let mut recv = words;
+        for word in recv.iter_mut().take(10) {
+            *word ^= 0b1111;
+        }
+        for i in 0..3 {
+            recv[30 + i] = golay::shortened::encode((words[30 + i] >> 12) as u8 ^ 0b111111);
+        }
+        let (dec, errs) = Header::decode(&recv).unwrap();
+        assert_eq!(dec, h);
+        assert_eq!(errs.erased, 10);
+        assert!(errs.rs >= 3);
+
+        let mut recv = words;
+        for i in 0..17 {
+            recv[i * 2] ^= 0b1111;
+        }
+        assert!(Header::decode(&recv).is_err());
+    }
+}
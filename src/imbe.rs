@@ -0,0 +1,329 @@
+//! Encoding and decoding of the 144-bit P25 Phase 1 IMBE voice frame, which protects the
+//! 88 prioritized vocoder parameter bits u<sub>0</sub>, ..., u<sub>7</sub>.
+//!
+//! Vectors u<sub>0</sub> through u<sub>3</sub> are protected by the (23, 12, 7) Golay
+//! code, u<sub>4</sub> through u<sub>6</sub> by the (15, 11, 3) Hamming code, and
+//! u<sub>7</sub> is sent uncoded. Coded vectors c<sub>1</sub> through c<sub>6</sub> are
+//! then scrambled with a pseudo-random sequence seeded from u<sub>0</sub>, and the frame
+//! is interleaved over 72 dibits.
+
+use crate::bits::Dibit;
+use crate::coding::{golay, hamming};
+use crate::error::DecodeError;
+
+/// Number of dibits in an IMBE frame.
+pub const DIBITS: usize = 72;
+
+/// Number of bits in each parameter vector.
+const DATA_BITS: [usize; 8] = [12, 12, 12, 12, 11, 11, 11, 7];
+
+/// Number of bits in each coded vector.
+const CODED_BITS: [usize; 8] = [23, 23, 23, 23, 15, 15, 15, 7];
+
+/// Frame bit position of each coded bit, where the coded vectors c<sub>0</sub>, ...,
+/// c<sub>7</sub> are taken in order and each MSB first, following the interleave table of
+/// TIA-102.BABA. Frame bit 2i is the high bit of dibit i and 2i + 1 is its low bit.
+const INTERLEAVE: [usize; 144] = [
+    0, 7, 12, 19, 24, 31, 36, 43, 48, 55, 60, 67, 72, 79, 84, 91, 96, 103, 108, 115, 120, 127, 132,
+    1, 6, 13, 18, 25, 30, 37, 42, 49, 54, 61, 66, 73, 78, 85, 90, 97, 102, 109, 114, 121, 126, 139,
+    2, 9, 14, 21, 26, 33, 38, 45, 50, 57, 62, 69, 74, 81, 86, 93, 98, 105, 110, 117, 122, 133, 138,
+    3, 8, 15, 20, 27, 32, 39, 44, 51, 56, 63, 68, 75, 80, 87, 92, 99, 104, 111, 116, 129, 134, 141,
+    4, 11, 16, 23, 28, 35, 40, 47, 52, 59, 64, 123, 128, 135, 140, 5, 10, 71, 76, 83, 88, 95, 100,
+    107, 112, 119, 124, 131, 136, 143, 17, 22, 29, 34, 41, 46, 53, 58, 65, 70, 77, 82, 89, 94, 101,
+    106, 113, 118, 125, 130, 137, 142,
+];
+
+/// Parameter vectors u<sub>0</sub>, ..., u<sub>7</sub> of an IMBE frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    /// Vectors u<sub>0</sub> through u<sub>3</sub> hold 12 bits, u<sub>4</sub> through
+    /// u<sub>6</sub> hold 11 bits, and u<sub>7</sub> holds 7 bits.
+    pub vectors: [u16; 8],
+}
+
+/// Bit errors corrected in each coded vector while decoding an IMBE frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Errors {
+    /// Number of corrected bits in c<sub>0</sub> through c<sub>6</sub>.
+    pub vectors: [usize; 7],
+}
+
+impl Errors {
+    /// Number of bits corrected in c<sub>0</sub> (ε<sub>0</sub>).
+    pub fn e0(&self) -> usize {
+        self.vectors[0]
+    }
+
+    /// Number of bits corrected in c<sub>4</sub> (ε<sub>4</sub>).
+    pub fn e4(&self) -> usize {
+        self.vectors[4]
+    }
+
+    /// Total number of bits corrected over every vector (ε<sub>T</sub>).
+    pub fn total(&self) -> usize {
+        self.vectors.iter().sum()
+    }
+}
+
+impl Frame {
+    /// Construct a new `Frame` from the given parameter vectors.
+    pub fn new(vectors: [u16; 8]) -> Frame {
+        for (&v, &bits) in vectors.iter().zip(DATA_BITS.iter()) {
+            assert_eq!(v >> bits, 0);
+        }
+
+        Frame { vectors }
+    }
+
+    /// Construct a new `Frame` from the given 88 parameter bits, with u<sub>0</sub> in
+    /// the MSBs of the first byte.
+    pub fn from_bytes(bytes: &[u8; 11]) -> Frame {
+        let mut bits = bytes
+            .iter()
+            .flat_map(|&b| (0..8).rev().map(move |i| (b >> i & 1) as u16));
+
+        let mut vectors = [0; 8];
+
+        for (v, &len) in vectors.iter_mut().zip(DATA_BITS.iter()) {
+            *v = (&mut bits).take(len).fold(0, |v, bit| v << 1 | bit);
+        }
+
+        Frame { vectors }
+    }
+
+    /// Convert the frame to its 88 parameter bits, with u<sub>0</sub> in the MSBs of the
+    /// first byte.
+    pub fn to_bytes(&self) -> [u8; 11] {
+        let mut bytes = [0; 11];
+
+        let bits = self
+            .vectors
+            .iter()
+            .zip(DATA_BITS.iter())
+            .flat_map(|(&v, &len)| (0..len).rev().map(move |i| (v >> i & 1) as u8));
+
+        for (i, bit) in bits.enumerate() {
+            bytes[i / 8] |= bit << (7 - i % 8);
+        }
+
+        bytes
+    }
+
+    /// Encode the frame into the 72 interleaved dibits of the voice frame.
+    pub fn encode(&self) -> [Dibit; DIBITS] {
+        let u = &self.vectors;
+        let pn = scramble(u[0]);
+
+        let coded = [
+            golay::standard::encode(u[0]),
+            golay::standard::encode(u[1]) ^ pn[0],
+            golay::standard::encode(u[2]) ^ pn[1],
+            golay::standard::encode(u[3]) ^ pn[2],
+            hamming::standard::encode(u[4]) as u32 ^ pn[3],
+            hamming::standard::encode(u[5]) as u32 ^ pn[4],
+            hamming::standard::encode(u[6]) as u32 ^ pn[5],
+            u[7] as u32,
+        ];
+
+        let mut bits = [0u8; 144];
+
+        for (&pos, bit) in INTERLEAVE.iter().zip(coded_bits(&coded)) {
+            bits[pos] = bit;
+        }
+
+        let mut dibits = [Dibit::default(); DIBITS];
+
+        for (dibit, pair) in dibits.iter_mut().zip(bits.chunks(2)) {
+            *dibit = Dibit::new(pair[0] << 1 | pair[1]);
+        }
+
+        dibits
+    }
+
+    /// Try to decode the given 72 interleaved dibits to the nearest frame.
+    ///
+    /// If decoding was successful, return `Ok((frame, errs))`, where `frame` is the
+    /// decoded frame and `errs` counts the bits corrected in each vector. Otherwise,
+    /// return an `Err` describing the unrecoverable error.
+    pub fn decode(dibits: &[Dibit; DIBITS]) -> Result<(Frame, Errors), DecodeError> {
+        let bits = dibits
+            .iter()
+            .flat_map(|d| [d.hi(), d.lo()])
+            .collect::<Vec<_>>();
+
+        let mut coded = [0u32; 8];
+        let mut pos = INTERLEAVE.iter();
+
+        for (c, &len) in coded.iter_mut().zip(CODED_BITS.iter()) {
+            *c = (&mut pos)
+                .take(len)
+                .fold(0, |c, &p| c << 1 | bits[p] as u32);
+        }
+
+        let mut vectors = [0; 8];
+        let mut errs = Errors::default();
+
+        // The first vector must be decoded to recover the scrambling sequence.
+        let (u0, err) = golay::standard::decode(coded[0])?;
+        vectors[0] = u0;
+        errs.vectors[0] = err;
+
+        let pn = scramble(u0);
+
+        for i in 1..4 {
+            let (u, err) = golay::standard::decode(coded[i] ^ pn[i - 1])?;
+            vectors[i] = u;
+            errs.vectors[i] = err;
+        }
+
+        for i in 4..7 {
+            let (u, err) = hamming::standard::decode((coded[i] ^ pn[i - 1]) as u16)?;
+            vectors[i] = u;
+            errs.vectors[i] = err;
+        }
+
+        vectors[7] = coded[7] as u16;
+
+        Ok((Frame { vectors }, errs))
+    }
+}
+
+/// Iterate over the bits of the given coded vectors, each MSB first.
+fn coded_bits(coded: &[u32; 8]) -> impl Iterator<Item = u8> + '_ {
+    coded
+        .iter()
+        .zip(CODED_BITS.iter())
+        .flat_map(|(&c, &len)| (0..len).rev().map(move |i| (c >> i & 1) as u8))
+}
+
+/// Generate the scrambling masks for coded vectors c<sub>1</sub> through c<sub>6</sub>
+/// from the pseudo-random sequence seeded by u<sub>0</sub>.
+fn scramble(u0: u16) -> [u32; 6] {
+    let mut p = 16 * u0 as u32;
+    let mut masks = [0; 6];
+
+    for (mask, &len) in masks.iter_mut().zip(CODED_BITS[1..7].iter()) {
+        for _ in 0..len {
+            p = (173 * p + 13849) % 65536;
+            *mask = *mask << 1 | p >> 15;
+        }
+    }
+
+    masks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame() -> Frame {
+        Frame::new([0xabc, 0x123, 0xfed, 0x0f0, 0x7ff, 0x2aa, 0x555, 0x5a])
+    }
+
+    #[test]
+    fn test_interleave() {
+        let mut seen = [false; 144];
+
+        for &pos in INTERLEAVE.iter() {
+            assert!(!seen[pos]);
+            seen[pos] = true;
+        }
+
+        // Start of each coded vector within INTERLEAVE.
+        let start = |v: usize| CODED_BITS[..v].iter().sum::<usize>();
+
+        // Dibits 0, 1, and 2 carry the MSBs of c0 and c1, c2 and c3, and c4 and c5.
+        assert_eq!(INTERLEAVE[start(0)], 0);
+        assert_eq!(INTERLEAVE[start(1)], 1);
+        assert_eq!(INTERLEAVE[start(2)], 2);
+        assert_eq!(INTERLEAVE[start(3)], 3);
+        assert_eq!(INTERLEAVE[start(4)], 4);
+        assert_eq!(INTERLEAVE[start(5)], 5);
+
+        // Dibit 3 carries the second bits of c1 and c0.
+        assert_eq!(INTERLEAVE[start(1) + 1], 6);
+        assert_eq!(INTERLEAVE[start(0) + 1], 7);
+
+        // The MSB of c6 is the low bit of dibit 8, and the MSB of c7 the high bit of
+        // dibit 53.
+        assert_eq!(INTERLEAVE[start(6)], 17);
+        assert_eq!(INTERLEAVE[start(7)], 106);
+
+        // The LSBs of c0 and c7 are the high bits of dibits 66 and 71.
+        assert_eq!(INTERLEAVE[start(1) - 1], 132);
+        assert_eq!(INTERLEAVE[143], 142);
+    }
+
+    #[test]
+    fn test_scramble() {
+        assert_eq!(scramble(0)[0] >> 22, 0);
+
+        let masks = scramble(0xabc);
+        for (&mask, &len) in masks.iter().zip(CODED_BITS[1..7].iter()) {
+            assert_eq!(mask >> len, 0);
+        }
+
+        // p(1) = 173·16·0xabc + 13849 mod 65536.
+        let p1 = (173 * 16 * 0xabc + 13849) % 65536;
+        assert_eq!(masks[0] >> 22, p1 >> 15);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let f = frame();
+        let bytes = f.to_bytes();
+
+        assert_eq!(bytes[0], 0xab);
+        assert_eq!(bytes[1], 0xc1);
+        assert_eq!(bytes[10], 0x80 | 0x5a);
+        assert_eq!(Frame::from_bytes(&bytes), f);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let f = frame();
+        let dibits = f.encode();
+
+        assert_eq!(Frame::decode(&dibits), Ok((f, Errors::default())));
+
+        // The first dibit carries the MSBs of c0 and c1.
+        let pn = scramble(0xabc);
+        assert_eq!(dibits[0].hi() as u32, golay::standard::encode(0xabc) >> 22);
+        assert_eq!(
+            dibits[0].lo() as u32,
+            (golay::standard::encode(0x123) ^ pn[0]) >> 22
+        );
+
+        let f = Frame::default();
+        assert_eq!(Frame::decode(&f.encode()), Ok((f, Errors::default())));
+    }
+
+    #[test]
+    fn test_errors() {
+        let f = frame();
+        let dibits = f.encode();
+
+        // Flip frame bits 0 and 7 (c0), 1 (c1), and 4 (c4).
+        let mut recv = dibits;
+        recv[0] = Dibit::new(recv[0].bits() ^ 0b11);
+        recv[3] = Dibit::new(recv[3].bits() ^ 0b01);
+        recv[2] = Dibit::new(recv[2].bits() ^ 0b10);
+
+        let (dec, errs) = Frame::decode(&recv).unwrap();
+        assert_eq!(dec, f);
+        assert_eq!(errs.vectors, [2, 1, 0, 0, 1, 0, 0]);
+        assert_eq!(errs.e0(), 2);
+        assert_eq!(errs.e4(), 1);
+        assert_eq!(errs.total(), 4);
+
+        // Too many errors in c0 decode to the wrong scrambling sequence.
+        let mut recv = dibits;
+        for i in [0, 3, 6, 9].iter() {
+            recv[*i] = Dibit::new(recv[*i].bits() ^ 0b11);
+        }
+
+        let (dec, _) = Frame::decode(&recv).unwrap();
+        assert_ne!(dec.vectors[0], f.vectors[0]);
+        assert_ne!(dec.vectors[1..7], f.vectors[1..7]);
+    }
+}
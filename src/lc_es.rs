@@ -0,0 +1,88 @@
+//! Framing shared by the link control (LC) word of the P25 LDU1 and the encryption sync
+//! (ES) word of the LDU2, where the word is protected by a (24, k) Reed-Solomon code and
+//! each resulting hexbit is further protected by the (10, 6, 3) shortened Hamming code.
+
+use crate::bits::{Hexbit, HexbitBytes, Hexbits};
+use crate::coding::hamming;
+use crate::error::DecodeError;
+
+/// Number of Hamming-coded words in the LC and ES fields.
+pub const WORDS: usize = 24;
+
+/// Errors corrected at each coding layer while decoding an LC or ES field.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Errors {
+    /// Number of bits corrected by the Hamming code across all words.
+    pub hamming: usize,
+    /// Number of Hamming words that couldn't be decoded and were passed to the
+    /// Reed-Solomon decoder as erasures.
+    pub erased: usize,
+    /// Number of hexbits corrected by the Reed-Solomon code, including filled erasures.
+    pub rs: usize,
+}
+
+/// Reed-Solomon encoder over the 24-hexbit buffer.
+pub(crate) type RsEncode = fn(&mut [Hexbit; WORDS]);
+
+/// Reed-Solomon decoder over the 24-hexbit buffer, with erasures.
+pub(crate) type RsDecode = for<'a, 'b> fn(
+    &'a mut [Hexbit; WORDS],
+    &'b [usize],
+) -> Result<(&'a [Hexbit], usize), DecodeError>;
+
+/// Encode the given data bytes with the given Reed-Solomon code and each resulting
+/// hexbit with the Hamming code, producing the 24 10-bit words in transmission order.
+pub(crate) fn encode(bytes: &[u8], rs: RsEncode) -> [u16; WORDS] {
+    let mut buf = [Hexbit::default(); WORDS];
+
+    for (dest, hexbit) in buf.iter_mut().zip(Hexbits::new(bytes.iter().cloned())) {
+        *dest = hexbit;
+    }
+
+    rs(&mut buf);
+
+    let mut words = [0; WORDS];
+
+    for (word, hexbit) in words.iter_mut().zip(buf.iter()) {
+        *word = hamming::shortened::encode(hexbit.bits());
+    }
+
+    words
+}
+
+/// Try to decode the given 24 10-bit words into the given data bytes. Words the Hamming
+/// code can't correct are passed to the given Reed-Solomon decoder as erasures.
+///
+/// If decoding was successful, return `Ok(errs)`, where `errs` counts the errors
+/// corrected at each layer. Otherwise, return an `Err` describing the unrecoverable
+/// error.
+pub(crate) fn decode(
+    words: &[u16; WORDS],
+    rs: RsDecode,
+    bytes: &mut [u8],
+) -> Result<Errors, DecodeError> {
+    let mut buf = [Hexbit::default(); WORDS];
+    let mut erasures = Vec::new();
+    let mut errs = Errors::default();
+
+    for (i, (dest, &word)) in buf.iter_mut().zip(words.iter()).enumerate() {
+        match hamming::shortened::decode(word) {
+            Ok((data, err)) => {
+                *dest = Hexbit::new(data);
+                errs.hamming += err;
+            }
+            Err(_) => erasures.push(i),
+        }
+    }
+
+    errs.erased = erasures.len();
+
+    let (data, err) = rs(&mut buf, &erasures)?;
+    errs.rs = err;
+
+    for (dest, byte) in bytes.iter_mut().zip(HexbitBytes::new(data.iter().cloned())) {
+        *dest = byte;
+    }
+
+    Ok(errs)
+}
@@ -0,0 +1,155 @@
+//! Encoding and decoding of the link control (LC) word carried by the P25 logical link
+//! data unit 1 (LDU1), protected by the (24, 12, 13) Reed-Solomon code, with each hexbit
+//! further protected by the (10, 6, 3) shortened Hamming code.
+
+use crate::coding::reed_solomon;
+use crate::error::DecodeError;
+use crate::lc_es;
+
+pub use crate::lc_es::{Errors, WORDS};
+
+/// Link control word.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkControl {
+    /// Link control format, made of the protected flag, the implicit MFID flag, and the
+    /// 6-bit opcode.
+    pub lcf: u8,
+    /// Manufacturer ID.
+    pub mfid: u8,
+    /// Format-specific fields.
+    pub data: [u8; 7],
+}
+
+impl LinkControl {
+    /// Encode the LC into the 24 10-bit Hamming codewords of the LDU1, in transmission
+    /// order, for 240 bits in total.
+    pub fn encode(&self) -> [u16; WORDS] {
+        lc_es::encode(&self.to_bytes(), reed_solomon::short::encode)
+    }
+
+    /// Try to decode the given 24 10-bit words to the nearest LC. Words the Hamming code
+    /// can't correct are treated as erasures by the Reed-Solomon code, so any combination
+    /// of e hexbit errors and f failed words with 2e + f ≤ 12 can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((lc, errs))`, where `lc` is the decoded LC
+    /// and `errs` counts the errors corrected at each layer. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(words: &[u16; WORDS]) -> Result<(LinkControl, Errors), DecodeError> {
+        let mut bytes = [0; 9];
+        let errs = lc_es::decode(words, reed_solomon::short::decode_with_erasures, &mut bytes)?;
+
+        Ok((LinkControl::from_bytes(&bytes), errs))
+    }
+
+    /// Pack the LC into its 72-bit transmitted form.
+    pub(crate) fn to_bytes(self) -> [u8; 9] {
+        let mut bytes = [0; 9];
+
+        bytes[0] = self.lcf;
+        bytes[1] = self.mfid;
+        bytes[2..].copy_from_slice(&self.data);
+
+        bytes
+    }
+
+    /// Unpack an LC from its 72-bit transmitted form.
+    pub(crate) fn from_bytes(bytes: &[u8; 9]) -> LinkControl {
+        let mut data = [0; 7];
+        data.copy_from_slice(&bytes[2..]);
+
+        LinkControl {
+            lcf: bytes[0],
+            mfid: bytes[1],
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coding::hamming;
+
+    fn lc() -> LinkControl {
+        LinkControl {
+            lcf: 0x00,
+            mfid: 0x00,
+            data: [0x00, 0x00, 0x01, 0x00, 0x12, 0x34, 0x56],
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let words = LinkControl::default().encode();
+        assert!(words.iter().all(|&w| w == 0));
+
+        let lc = lc();
+        let words = lc.encode();
+        assert!(words.iter().all(|&w| w >> 10 == 0));
+
+        // The last data hexbits carry the source ID, MSB first.
+        assert_eq!(
+            words[10],
+            hamming::shortened::encode((0x34 & 0b1111) << 2 | 0x56 >> 6)
+        );
+        assert_eq!(words[11], hamming::shortened::encode(0x56 & 0b111111));
+
+        assert_eq!(LinkControl::decode(&words), Ok((lc, Errors::default())));
+    }
+
+    #[test]
+    fn test_decode() {
+        let lc = lc();
+        let words = lc.encode();
+
+        // Errors within the Hamming correction radius.
+        let mut recv = words;
+        recv[0] ^= 1 << 9;
+        recv[5] ^= 1 << 4;
+        recv[23] ^= 1;
+        assert_eq!(
+            LinkControl::decode(&recv),
+            Ok((
+                lc,
+                Errors {
+                    hamming: 3,
+                    erased: 0,
+                    rs: 0
+                }
+            ))
+        );
+
+        // Words replaced by other codewords pass the Hamming layer untouched.
+        let mut recv = words;
+        for i in 0..6 {
+            recv[i * 4] = hamming::shortened::encode((recv[i * 4] >> 4) as u8 ^ 0b100001);
+        }
+        assert_eq!(
+            LinkControl::decode(&recv),
+            Ok((
+                lc,
+                Errors {
+                    hamming: 0,
+                    erased: 0,
+                    rs: 6
+                }
+            ))
+        );
+
+        // Words the Hamming code can't locate an error in become erasures.
+        let bad = (0..1 << 10)
+            .find(|&w| hamming::shortened::decode(w).is_err())
+            .unwrap();
+
+        let mut recv = words;
+        for i in 0..12 {
+            recv[i * 2] = bad;
+        }
+        let (dec, errs) = LinkControl::decode(&recv).unwrap();
+        assert_eq!(dec, lc);
+        assert_eq!(errs.erased, 12);
+
+        recv[1] = bad;
+        assert!(LinkControl::decode(&recv).is_err());
+    }
+}
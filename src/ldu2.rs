@@ -0,0 +1,140 @@
+//! Encoding and decoding of the encryption sync (ES) word carried by the P25 logical link
+//! data unit 2 (LDU2), protected by the (24, 16, 9) Reed-Solomon code, with each hexbit
+//! further protected by the (10, 6, 3) shortened Hamming code.
+
+use crate::coding::reed_solomon;
+use crate::error::DecodeError;
+use crate::lc_es;
+
+pub use crate::lc_es::{Errors, WORDS};
+
+/// Encryption sync word.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EncryptionSync {
+    /// 72-bit message indicator, the initialization vector for encryption.
+    pub mi: [u8; 9],
+    /// Encryption algorithm ID.
+    pub algid: u8,
+    /// Encryption key ID.
+    pub kid: u16,
+}
+
+impl EncryptionSync {
+    /// Encode the ES into the 24 10-bit Hamming codewords of the LDU2, in transmission
+    /// order, for 240 bits in total.
+    pub fn encode(&self) -> [u16; WORDS] {
+        lc_es::encode(&self.to_bytes(), reed_solomon::medium::encode)
+    }
+
+    /// Try to decode the given 24 10-bit words to the nearest ES. Words the Hamming code
+    /// can't correct are treated as erasures by the Reed-Solomon code, so any combination
+    /// of e hexbit errors and f failed words with 2e + f ≤ 8 can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((es, errs))`, where `es` is the decoded ES
+    /// and `errs` counts the errors corrected at each layer. Otherwise, return an `Err`
+    /// describing the unrecoverable error.
+    pub fn decode(words: &[u16; WORDS]) -> Result<(EncryptionSync, Errors), DecodeError> {
+        let mut bytes = [0; 12];
+        let errs = lc_es::decode(
+            words,
+            reed_solomon::medium::decode_with_erasures,
+            &mut bytes,
+        )?;
+
+        Ok((EncryptionSync::from_bytes(&bytes), errs))
+    }
+
+    /// Pack the ES into its 96-bit transmitted form.
+    fn to_bytes(self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+
+        bytes[..9].copy_from_slice(&self.mi);
+        bytes[9] = self.algid;
+        bytes[10] = (self.kid >> 8) as u8;
+        bytes[11] = self.kid as u8;
+
+        bytes
+    }
+
+    /// Unpack an ES from its 96-bit transmitted form.
+    fn from_bytes(bytes: &[u8; 12]) -> EncryptionSync {
+        let mut mi = [0; 9];
+        mi.copy_from_slice(&bytes[..9]);
+
+        EncryptionSync {
+            mi,
+            algid: bytes[9],
+            kid: (bytes[10] as u16) << 8 | bytes[11] as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coding::hamming;
+
+    fn es() -> EncryptionSync {
+        EncryptionSync {
+            mi: [0xde, 0xad, 0xbe, 0xef, 0x01, 0x23, 0x45, 0x67, 0x89],
+            algid: 0xaa,
+            kid: 0x55a5,
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let es = es();
+        let words = es.encode();
+        assert!(words.iter().all(|&w| w >> 10 == 0));
+
+        assert_eq!(words[0], hamming::shortened::encode(0xde >> 2));
+        assert_eq!(words[15], hamming::shortened::encode(0xa5 & 0b111111));
+
+        assert_eq!(EncryptionSync::decode(&words), Ok((es, Errors::default())));
+    }
+
+    #[test]
+    fn test_decode() {
+        let es = es();
+        let words = es.encode();
+
+        let mut recv = words;
+        recv[3] ^= 1 << 6;
+        recv[8] = hamming::shortened::encode((recv[8] >> 4) as u8 ^ 1);
+        recv[20] = hamming::shortened::encode((recv[20] >> 4) as u8 ^ 0b10);
+        assert_eq!(
+            EncryptionSync::decode(&recv),
+            Ok((
+                es,
+                Errors {
+                    hamming: 1,
+                    erased: 0,
+                    rs: 2
+                }
+            ))
+        );
+
+        let bad = (0..1 << 10)
+            .find(|&w| hamming::shortened::decode(w).is_err())
+            .unwrap();
+
+        let mut recv = words;
+        for i in 0..8 {
+            recv[i * 3] = bad;
+        }
+        let (dec, errs) = EncryptionSync::decode(&recv).unwrap();
+        assert_eq!(dec, es);
+        assert_eq!(errs.erased, 8);
+
+        // With every parity hexbit spent on erasures, a further error goes undetected.
+        recv[1] = hamming::shortened::encode((recv[1] >> 4) as u8 ^ 1);
+        assert_ne!(EncryptionSync::decode(&recv).map(|(es, _)| es), Ok(es));
+
+        recv[2] = bad;
+        assert_eq!(
+            EncryptionSync::decode(&recv),
+            Err(DecodeError::TooManyErasures)
+        );
+    }
+}
@@ -1,2 +1,15 @@
 pub mod bits;
 pub mod coding;
+mod data_block;
+pub mod error;
+pub mod frame;
+pub mod hdu;
+pub mod imbe;
+mod lc_es;
+pub mod ldu1;
+pub mod ldu2;
+pub mod nid;
+pub mod pdu;
+pub mod sim;
+pub mod tdulc;
+pub mod tsbk;
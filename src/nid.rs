@@ -0,0 +1,280 @@
+//! Encoding and decoding of the P25 network ID (NID) word, which carries the network
+//! access code (NAC) and data unit ID (DUID) protected by the (64, 16, 23) BCH code.
+
+use crate::coding::bch;
+use crate::error::DecodeError;
+
+/// Data unit ID, identifying the type of the data unit following the NID.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Duid {
+    /// Header data unit.
+    Hdu,
+    /// Simple terminator data unit.
+    Tdu,
+    /// Logical link data unit 1.
+    Ldu1,
+    /// Trunking signalling block.
+    Tsbk,
+    /// Logical link data unit 2.
+    Ldu2,
+    /// Packet data unit.
+    Pdu,
+    /// Terminator data unit with link control.
+    Tdulc,
+}
+
+impl Duid {
+    /// Parse the given 4-bit DUID, returning `None` if it isn't a defined data unit.
+    pub fn from_bits(bits: u8) -> Option<Duid> {
+        use self::Duid::*;
+
+        match bits {
+            0b0000 => Some(Hdu),
+            0b0011 => Some(Tdu),
+            0b0101 => Some(Ldu1),
+            0b0111 => Some(Tsbk),
+            0b1010 => Some(Ldu2),
+            0b1100 => Some(Pdu),
+            0b1111 => Some(Tdulc),
+            _ => None,
+        }
+    }
+
+    /// Convert the DUID to its 4-bit value.
+    pub fn to_bits(self) -> u8 {
+        use self::Duid::*;
+
+        match self {
+            Hdu => 0b0000,
+            Tdu => 0b0011,
+            Ldu1 => 0b0101,
+            Tsbk => 0b0111,
+            Ldu2 => 0b1010,
+            Pdu => 0b1100,
+            Tdulc => 0b1111,
+        }
+    }
+}
+
+/// Network ID word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Nid {
+    /// 12-bit network access code.
+    pub nac: u16,
+    /// Type of the following data unit.
+    pub duid: Duid,
+}
+
+impl Nid {
+    /// Construct a new `Nid` from the given 12-bit NAC and DUID.
+    pub fn new(nac: u16, duid: Duid) -> Nid {
+        assert_eq!(nac >> 12, 0);
+        Nid { nac, duid }
+    }
+
+    /// Encode the NID into the 64-bit word made of the 63-bit BCH codeword followed by
+    /// the P25 parity bit, which is set for LDU1 and LDU2 and clear for every other data
+    /// unit.
+    pub fn encode(&self) -> u64 {
+        bch::encode(self.nac << 4 | self.duid.to_bits() as u16)
+    }
+
+    /// Try to decode the given 64-bit word to the nearest NID, correcting up to 11 bit
+    /// errors in the BCH codeword.
+    ///
+    /// If decoding was successful, return `Ok((nid, err))`, where `nid` is the decoded
+    /// NID and `err` is the number of corrected bits, which includes the parity bit if it
+    /// disagrees with the decoded DUID. Otherwise, return an `Err` describing the
+    /// unrecoverable error, with `DecodeError::InvalidData` indicating an undefined DUID.
+    pub fn decode(word: u64) -> Result<(Nid, usize), DecodeError> {
+        let (data, err) = bch::decode(word)?;
+        let duid = Duid::from_bits(data as u8 & 0b1111).ok_or(DecodeError::InvalidData)?;
+
+        // The parity bit isn't covered by the BCH code, so check it against the parity
+        // expected for the decoded DUID.
+        let parity = (bch::encode(data) ^ word) & 1;
+
+        Ok((Nid::new(data >> 4, duid), err + parity as usize))
+    }
+}
+
+/// Candidate NID matched against a received word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NidMatch {
+    /// Closest NID to the received word.
+    pub nid: Nid,
+    /// Hamming distance from the received word to the NID's 64-bit word.
+    pub distance: usize,
+    /// Difference between the distance to the next closest candidate and `distance`.
+    pub margin: usize,
+}
+
+/// All defined data unit IDs.
+const DUIDS: [Duid; 7] = [
+    Duid::Hdu,
+    Duid::Tdu,
+    Duid::Ldu1,
+    Duid::Tsbk,
+    Duid::Ldu2,
+    Duid::Pdu,
+    Duid::Tdulc,
+];
+
+impl Nid {
+    /// Decode the given 64-bit word by scoring it against the words of every defined DUID
+    /// for each of the given expected NACs, which can recover NIDs with more errors than
+    /// the BCH code can correct.
+    ///
+    /// If the closest candidate is within the given Hamming distance radius of the
+    /// received word and closer than every other candidate, return `Ok(m)`, where `m`
+    /// holds the candidate, its distance, and its margin over the next closest
+    /// candidate. Otherwise, return `Err(DecodeError::Uncorrectable)` if no candidate is
+    /// within the radius or `Err(DecodeError::Ambiguous)` if two distinct candidates tie
+    /// for closest within the radius. Repeated NACs are only considered once.
+    pub fn decode_known(word: u64, nacs: &[u16], radius: usize) -> Result<NidMatch, DecodeError> {
+        let mut best: Option<(Nid, usize)> = None;
+        let mut next = usize::MAX;
+
+        for (i, &nac) in nacs.iter().enumerate() {
+            if nacs[..i].contains(&nac) {
+                continue;
+            }
+
+            for &duid in DUIDS.iter() {
+                let nid = Nid::new(nac, duid);
+                let dist = (nid.encode() ^ word).count_ones() as usize;
+
+                match best {
+                    Some((_, min)) if dist >= min => next = next.min(dist),
+                    Some((_, min)) => {
+                        next = min;
+                        best = Some((nid, dist));
+                    }
+                    None => best = Some((nid, dist)),
+                }
+            }
+        }
+
+        match best {
+            Some((_, distance)) if distance > radius => Err(DecodeError::Uncorrectable),
+            Some((_, distance)) if distance == next => Err(DecodeError::Ambiguous),
+            Some((nid, distance)) => Ok(NidMatch {
+                nid,
+                distance,
+                margin: next - distance,
+            }),
+            None => Err(DecodeError::Uncorrectable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duid() {
+        for bits in 0..16 {
+            if let Some(duid) = Duid::from_bits(bits) {
+                assert_eq!(duid.to_bits(), bits);
+            }
+        }
+
+        assert_eq!(Duid::from_bits(0b0101), Some(Duid::Ldu1));
+        assert_eq!(Duid::from_bits(0b0001), None);
+    }
+
+    #[test]
+    fn test_parity() {
+        for &duid in [
+            Duid::Hdu,
+            Duid::Tdu,
+            Duid::Ldu1,
+            Duid::Tsbk,
+            Duid::Ldu2,
+            Duid::Pdu,
+            Duid::Tdulc,
+        ]
+        .iter()
+        {
+            let word = Nid::new(0x293, duid).encode();
+            assert_eq!(word & 1 == 1, duid == Duid::Ldu1 || duid == Duid::Ldu2);
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let nid = Nid::new(0x293, Duid::Tsbk);
+        let word = nid.encode();
+
+        assert_eq!(word >> 48, 0x2937);
+        assert_eq!(Nid::decode(word), Ok((nid, 0)));
+        assert_eq!(Nid::decode(word ^ 1), Ok((nid, 1)));
+        assert_eq!(Nid::decode(word ^ 0b1111 << 40 ^ 1 << 63), Ok((nid, 5)));
+        assert_eq!(Nid::decode(word ^ 0b111_1111_1111 << 30 ^ 1), Ok((nid, 12)));
+
+        // A valid BCH codeword with an undefined DUID.
+        assert_eq!(
+            Nid::decode(bch::encode(0x2931)),
+            Err(DecodeError::InvalidData)
+        );
+
+        assert!(Nid::decode(word ^ 0xffff_ffff_0000_0000).is_err());
+    }
+
+    #[test]
+    fn test_decode_known() {
+        let nid = Nid::new(0x293, Duid::Ldu1);
+        let word = nid.encode();
+
+        let m = Nid::decode_known(word, &[0x293], 20).unwrap();
+        assert_eq!(m.nid, nid);
+        assert_eq!(m.distance, 0);
+        assert!(m.margin >= 23);
+
+        // Beyond the BCH correction radius.
+        let recv = word ^ 0x8421_8421_8421_8420;
+        assert_eq!((recv ^ word).count_ones(), 15);
+        assert_ne!(Nid::decode(recv).map(|(n, _)| n), Ok(nid));
+
+        let m = Nid::decode_known(recv, &[0x123, 0x293, 0xf00], 16).unwrap();
+        assert_eq!(m.nid, nid);
+        assert_eq!(m.distance, 15);
+        assert!(m.margin > 0);
+
+        assert_eq!(
+            Nid::decode_known(recv, &[0x293], 14),
+            Err(DecodeError::Uncorrectable)
+        );
+        assert_eq!(
+            Nid::decode_known(recv, &[], 64),
+            Err(DecodeError::Uncorrectable)
+        );
+
+        // The same NAC given twice doesn't tie with itself.
+        assert_eq!(
+            Nid::decode_known(recv, &[0x293, 0x293], 16),
+            Nid::decode_known(recv, &[0x293], 16)
+        );
+
+        // A word halfway between two NIDs is only ambiguous when both are within the
+        // radius.
+        let other = Nid::new(0x293, Duid::Ldu2).encode();
+        let diff = word ^ other;
+        let half = (0..64)
+            .filter(|i| diff >> i & 1 == 1)
+            .take(diff.count_ones() as usize / 2)
+            .fold(0, |m, i| m | 1 << i);
+        let recv = word ^ half;
+        let dist = half.count_ones() as usize;
+        assert_eq!((recv ^ other).count_ones() as usize, dist);
+        assert_eq!(
+            Nid::decode_known(recv, &[0x293], dist),
+            Err(DecodeError::Ambiguous)
+        );
+        assert_eq!(
+            Nid::decode_known(recv, &[0x293], dist - 1),
+            Err(DecodeError::Uncorrectable)
+        );
+    }
+}
@@ -0,0 +1,522 @@
+//! Encoding and decoding of P25 packet data units (PDUs), which carry a header block
+//! followed by confirmed or unconfirmed data blocks, with the packet protected as a whole
+//! by a 32-bit CRC.
+//!
+//! The header and unconfirmed data blocks are half-rate trellis coded, and confirmed data
+//! blocks are 3/4-rate trellis coded with a serial number and 9-bit CRC so failed blocks
+//! can be selectively retried.
+
+use crate::bits::Dibit;
+use crate::coding::crc::{CRC_32, CRC_9, CRC_CCITT};
+use crate::coding::interleave::p25_data;
+use crate::coding::trellis::DibitMetric;
+use crate::data_block::{self, HALF_BYTES, THREE_QUARTER_BYTES};
+use crate::error::DecodeError;
+
+/// Number of transmitted dibits in each PDU block.
+pub const DIBITS: usize = p25_data::DIBITS;
+
+/// Number of data bytes carried by a confirmed data block.
+pub const CONFIRMED_BYTES: usize = 16;
+
+/// Number of data bytes carried by an unconfirmed data block.
+pub const UNCONFIRMED_BYTES: usize = 12;
+
+/// Maximum number of data blocks following a header.
+pub const MAX_BLOCKS: usize = 127;
+
+/// Number of bytes taken by the packet CRC at the end of the last data block.
+const PACKET_CRC_BYTES: usize = 4;
+
+/// Maximum number of payload bytes carried by a confirmed PDU.
+pub const MAX_CONFIRMED_PAYLOAD: usize = MAX_BLOCKS * CONFIRMED_BYTES - PACKET_CRC_BYTES;
+
+/// Maximum number of payload bytes carried by an unconfirmed PDU.
+pub const MAX_UNCONFIRMED_PAYLOAD: usize = MAX_BLOCKS * UNCONFIRMED_BYTES - PACKET_CRC_BYTES;
+
+/// Number of serial number bits in a confirmed data block.
+const SERIAL_BITS: u8 = 7;
+
+/// Type of data blocks following the header.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Confirmed data blocks, acknowledged by the receiver.
+    Confirmed,
+    /// Unconfirmed data blocks.
+    Unconfirmed,
+}
+
+impl Format {
+    /// Parse the given 5-bit format, returning `None` if it isn't a data block format.
+    pub fn from_bits(bits: u8) -> Option<Format> {
+        match bits {
+            0b10110 => Some(Format::Confirmed),
+            0b10101 => Some(Format::Unconfirmed),
+            _ => None,
+        }
+    }
+
+    /// Convert the format to its 5-bit value.
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Format::Confirmed => 0b10110,
+            Format::Unconfirmed => 0b10101,
+        }
+    }
+
+    /// Number of data bytes carried by each block of this format.
+    pub fn block_bytes(self) -> usize {
+        match self {
+            Format::Confirmed => CONFIRMED_BYTES,
+            Format::Unconfirmed => UNCONFIRMED_BYTES,
+        }
+    }
+
+    /// Maximum number of payload bytes carried by a PDU of this format.
+    pub fn max_payload(self) -> usize {
+        match self {
+            Format::Confirmed => MAX_CONFIRMED_PAYLOAD,
+            Format::Unconfirmed => MAX_UNCONFIRMED_PAYLOAD,
+        }
+    }
+}
+
+/// PDU header.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// Type of the following data blocks, which also determines whether a response is
+    /// requested.
+    pub format: Format,
+    /// Whether the packet is sent outbound from the fixed network.
+    pub outbound: bool,
+    /// 6-bit service access point.
+    pub sap: u8,
+    /// Manufacturer ID.
+    pub mfid: u8,
+    /// 24-bit logical link ID of the source or destination.
+    pub llid: u32,
+    /// Whether this is the first transmission of the full packet, rather than a retry.
+    pub full_message: bool,
+    /// Number of data blocks following the header.
+    pub blocks: u8,
+    /// Number of zero pad bytes at the end of the payload, before the packet CRC.
+    pub pad: u8,
+    /// Whether the receiver should resynchronize its sequence numbers.
+    pub resync: bool,
+    /// 3-bit packet sequence number.
+    pub seq: u8,
+    /// 4-bit fragment sequence number field.
+    pub frag: u8,
+    /// 6-bit offset of the payload past any data header.
+    pub offset: u8,
+}
+
+impl Header {
+    /// Construct a new `Header` for a packet of the given format, sent to or from the
+    /// given service access point and logical link ID. The block and pad counts are
+    /// filled in when the packet is encoded.
+    pub fn new(format: Format, sap: u8, llid: u32) -> Header {
+        assert_eq!(sap >> 6, 0);
+        assert_eq!(llid >> 24, 0);
+
+        Header {
+            format,
+            outbound: false,
+            sap,
+            mfid: 0,
+            llid,
+            full_message: true,
+            blocks: 0,
+            pad: 0,
+            resync: false,
+            seq: 0,
+            frag: 0,
+            offset: 0,
+        }
+    }
+
+    /// Encode the header into the 98 interleaved dibits of the header block.
+    pub fn encode(&self) -> [Dibit; DIBITS] {
+        let mut bytes = [0; HALF_BYTES];
+
+        bytes[0] = ((self.format == Format::Confirmed) as u8) << 6
+            | (self.outbound as u8) << 5
+            | self.format.to_bits();
+        bytes[1] = 0b1100_0000 | self.sap;
+        bytes[2] = self.mfid;
+        bytes[3] = (self.llid >> 16) as u8;
+        bytes[4] = (self.llid >> 8) as u8;
+        bytes[5] = self.llid as u8;
+        bytes[6] = (self.full_message as u8) << 7 | self.blocks;
+        bytes[7] = self.pad;
+        bytes[8] = (self.resync as u8) << 7 | self.seq << 4 | self.frag;
+        bytes[9] = self.offset;
+
+        let crc = CRC_CCITT.checksum(&bytes[..10]);
+        bytes[10] = (crc >> 8) as u8;
+        bytes[11] = crc as u8;
+
+        data_block::encode_half(&bytes)
+    }
+
+    /// Try to decode the given 98 interleaved received dibits, which may be hard `Dibit`
+    /// decisions or any soft-decision type implementing `DibitMetric`, into a header.
+    ///
+    /// If decoding was successful, return `Ok(header)`. Otherwise, return
+    /// `Err(DecodeError::DetectedUncorrectable)` if the header CRC failed or
+    /// `Err(DecodeError::InvalidData)` if the header doesn't introduce data blocks.
+    pub fn decode<M: DibitMetric + Copy>(dibits: &[M; DIBITS]) -> Result<Header, DecodeError> {
+        let (bytes, _) = data_block::decode_half(dibits);
+
+        if CRC_CCITT.checksum(&bytes[..10]) != (bytes[10] as u32) << 8 | bytes[11] as u32 {
+            return Err(DecodeError::DetectedUncorrectable);
+        }
+
+        let format = Format::from_bits(bytes[0] & 0b11111).ok_or(DecodeError::InvalidData)?;
+
+        Ok(Header {
+            format,
+            outbound: bytes[0] >> 5 & 1 == 1,
+            sap: bytes[1] & 0b111111,
+            mfid: bytes[2],
+            llid: (bytes[3] as u32) << 16 | (bytes[4] as u32) << 8 | bytes[5] as u32,
+            full_message: bytes[6] >> 7 == 1,
+            blocks: bytes[6] & 0b111_1111,
+            pad: bytes[7] & 0b11111,
+            resync: bytes[8] >> 7 == 1,
+            seq: bytes[8] >> 4 & 0b111,
+            frag: bytes[8] & 0b1111,
+            offset: bytes[9] & 0b111111,
+        })
+    }
+}
+
+/// Decoded PDU.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// Decoded header.
+    pub header: Header,
+    /// Decoded payload, excluding pad bytes and the packet CRC. Bytes from failed blocks
+    /// are included as decoded.
+    pub payload: Vec<u8>,
+    /// Index of each data block that failed to decode or was missing, which for
+    /// confirmed data is also the block's serial number modulo 128.
+    pub failed: Vec<usize>,
+    /// Whether the packet CRC calculated over the decoded blocks matched the decoded
+    /// packet CRC.
+    pub crc_ok: bool,
+}
+
+/// Encode the given payload into a PDU. The block and pad counts of the given header are
+/// replaced with those of the payload.
+///
+/// If encoding was successful, return `Ok(blocks)`, where `blocks` holds the header block
+/// followed by each data block as 98 interleaved dibits. Otherwise, return
+/// `Err(DecodeError::InvalidData)` if the payload is longer than
+/// `header.format.max_payload()` bytes.
+pub fn encode(header: &Header, payload: &[u8]) -> Result<Vec<[Dibit; DIBITS]>, DecodeError> {
+    if payload.len() > header.format.max_payload() {
+        return Err(DecodeError::InvalidData);
+    }
+
+    let size = header.format.block_bytes();
+    let blocks = (payload.len() + PACKET_CRC_BYTES).div_ceil(size);
+
+    let mut data = payload.to_vec();
+    data.resize(blocks * size - PACKET_CRC_BYTES, 0);
+
+    let crc = CRC_32.checksum(&data);
+    data.extend_from_slice(&crc.to_be_bytes());
+
+    let header = Header {
+        blocks: blocks as u8,
+        pad: (data.len() - PACKET_CRC_BYTES - payload.len()) as u8,
+        ..*header
+    };
+
+    Ok(std::iter::once(header.encode())
+        .chain(
+            data.chunks(size)
+                .enumerate()
+                .map(|(serial, chunk)| match header.format {
+                    Format::Confirmed => encode_confirmed(serial as u8, chunk),
+                    Format::Unconfirmed => encode_unconfirmed(chunk),
+                }),
+        )
+        .collect())
+}
+
+/// Try to decode the given received PDU, made of the header block followed by each data
+/// block as 98 interleaved dibits, which may be hard `Dibit` decisions or any
+/// soft-decision type implementing `DibitMetric`.
+///
+/// If the header was decoded, return `Ok(pdu)`, where `pdu` holds the payload along with
+/// the blocks that failed and whether the packet CRC passed. Otherwise, return an `Err`
+/// describing why the header couldn't be decoded.
+pub fn decode<M: DibitMetric + Copy>(blocks: &[[M; DIBITS]]) -> Result<Decoded, DecodeError> {
+    let (first, rest) = blocks.split_first().ok_or(DecodeError::InvalidData)?;
+    let header = Header::decode(first)?;
+
+    let size = header.format.block_bytes();
+    let count = header.blocks as usize;
+    let len = count * size;
+
+    if len < PACKET_CRC_BYTES + header.pad as usize {
+        return Err(DecodeError::InvalidData);
+    }
+
+    let mut data = vec![0; len];
+    let mut failed = Vec::new();
+
+    for (serial, chunk) in data.chunks_mut(size).enumerate() {
+        let ok = match rest.get(serial) {
+            Some(block) => match header.format {
+                Format::Confirmed => decode_confirmed(block, serial as u8, chunk),
+                Format::Unconfirmed => decode_unconfirmed(block, chunk),
+            },
+            None => false,
+        };
+
+        if !ok {
+            failed.push(serial);
+        }
+    }
+
+    let (data, crc) = data.split_at(len - PACKET_CRC_BYTES);
+    let crc = u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]);
+
+    Ok(Decoded {
+        header,
+        payload: data[..data.len() - header.pad as usize].to_vec(),
+        failed,
+        crc_ok: CRC_32.checksum(data) == crc,
+    })
+}
+
+/// Encode the given data bytes into a confirmed data block with the given serial number.
+fn encode_confirmed(serial: u8, data: &[u8]) -> [Dibit; DIBITS] {
+    let serial = serial & 0b111_1111;
+    let crc = CRC_9.checksum_bits(confirmed_bits(serial, data));
+
+    let mut bytes = [0; THREE_QUARTER_BYTES];
+    bytes[0] = serial << 1 | (crc >> 8) as u8;
+    bytes[1] = crc as u8;
+    bytes[2..].copy_from_slice(data);
+
+    data_block::encode_three_quarter(&bytes)
+}
+
+/// Decode the given received confirmed data block into the given data bytes, returning
+/// whether the block's CRC passed and its serial number matched the expected serial
+/// number.
+fn decode_confirmed<M>(dibits: &[M; DIBITS], expected: u8, data: &mut [u8]) -> bool
+where
+    M: DibitMetric + Copy,
+{
+    let (bytes, _) = data_block::decode_three_quarter(dibits);
+    data.copy_from_slice(&bytes[2..]);
+
+    let serial = bytes[0] >> 1;
+    let crc = (bytes[0] as u32 & 1) << 8 | bytes[1] as u32;
+
+    CRC_9.checksum_bits(confirmed_bits(serial, data)) == crc && serial == expected & 0b111_1111
+}
+
+/// Iterate over the bits covered by a confirmed data block's CRC, made of the 7-bit
+/// serial number followed by the data bytes.
+fn confirmed_bits(serial: u8, data: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    (0..SERIAL_BITS)
+        .rev()
+        .map(move |i| serial >> i & 1 == 1)
+        .chain(
+            data.iter()
+                .flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)),
+        )
+}
+
+/// Encode the given data bytes into an unconfirmed data block.
+fn encode_unconfirmed(data: &[u8]) -> [Dibit; DIBITS] {
+    let mut bytes = [0; HALF_BYTES];
+    bytes.copy_from_slice(data);
+
+    data_block::encode_half(&bytes)
+}
+
+/// Decode the given received unconfirmed data block into the given data bytes, returning
+/// whether every symbol was decoded unambiguously.
+fn decode_unconfirmed<M>(dibits: &[M; DIBITS], data: &mut [u8]) -> bool
+where
+    M: DibitMetric + Copy,
+{
+    let (bytes, ambiguous) = data_block::decode_half(dibits);
+    data.copy_from_slice(&bytes);
+
+    ambiguous.is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn payload(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7 + 3) as u8).collect()
+    }
+
+    #[test]
+    fn test_header() {
+        let mut header = Header::new(Format::Confirmed, 0x20, 0x123456);
+        header.outbound = true;
+        header.mfid = 0x90;
+        header.blocks = 3;
+        header.pad = 12;
+        header.resync = true;
+        header.seq = 5;
+        header.frag = 0b1001;
+        header.offset = 0x21;
+
+        let dibits = header.encode();
+        assert_eq!(Header::decode(&dibits), Ok(header));
+
+        let header = Header::new(Format::Unconfirmed, 0x3f, 0xffffff);
+        assert_eq!(Header::decode(&header.encode()), Ok(header));
+
+        // A valid block that isn't a data header.
+        let mut bytes = [0; HALF_BYTES];
+        let crc = CRC_CCITT.checksum(&bytes[..10]);
+        bytes[10] = (crc >> 8) as u8;
+        bytes[11] = crc as u8;
+        assert_eq!(
+            Header::decode(&data_block::encode_half(&bytes)),
+            Err(DecodeError::InvalidData)
+        );
+
+        bytes[11] ^= 1;
+        assert_eq!(
+            Header::decode(&data_block::encode_half(&bytes)),
+            Err(DecodeError::DetectedUncorrectable)
+        );
+    }
+
+    #[test]
+    fn test_confirmed() {
+        let header = Header::new(Format::Confirmed, 4, 0x1234);
+
+        for &len in [0, 1, 12, 13, 28, 29, 60].iter() {
+            let payload = payload(len);
+            let blocks = encode(&header, &payload).unwrap();
+
+            assert_eq!(blocks.len(), 1 + (len + 4).div_ceil(16));
+
+            let dec = decode(&blocks).unwrap();
+            assert_eq!(dec.payload, payload);
+            assert!(dec.failed.is_empty());
+            assert!(dec.crc_ok);
+            assert_eq!(dec.header.blocks as usize, blocks.len() - 1);
+            assert_eq!(
+                dec.header.pad as usize,
+                dec.header.blocks as usize * 16 - 4 - len
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_payload() {
+        for &format in [Format::Confirmed, Format::Unconfirmed].iter() {
+            let header = Header::new(format, 4, 0x1234);
+            let payload = payload(format.max_payload());
+            let blocks = encode(&header, &payload).unwrap();
+
+            assert_eq!(blocks.len(), 1 + MAX_BLOCKS);
+            assert_eq!(decode(&blocks).unwrap().payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_payload_too_long() {
+        let header = Header::new(Format::Unconfirmed, 4, 0x1234);
+        assert_eq!(
+            encode(&header, &payload(MAX_UNCONFIRMED_PAYLOAD + 1)),
+            Err(DecodeError::InvalidData)
+        );
+
+        let header = Header::new(Format::Confirmed, 4, 0x1234);
+        assert_eq!(
+            encode(&header, &payload(MAX_CONFIRMED_PAYLOAD + 1)),
+            Err(DecodeError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_confirmed_failed() {
+        let header = Header::new(Format::Confirmed, 4, 0x1234);
+        let payload = payload(60);
+        let mut blocks = encode(&header, &payload).unwrap();
+
+        // Blocks 1 and 3 are corrupted beyond repair.
+        for i in 0..40 {
+            blocks[2][i] = Dibit::new(blocks[2][i].bits() ^ 0b11);
+            blocks[4][i * 2] = Dibit::new(blocks[4][i * 2].bits() ^ 0b10);
+        }
+
+        let dec = decode(&blocks).unwrap();
+        assert_eq!(dec.failed, vec![1, 3]);
+        assert!(!dec.crc_ok);
+
+        // A block repeated in place of another has the wrong serial number.
+        let mut blocks = encode(&header, &payload).unwrap();
+        blocks[1] = blocks[2];
+        let dec = decode(&blocks).unwrap();
+        assert_eq!(dec.failed, vec![0]);
+
+        // Missing blocks fail.
+        let blocks = encode(&header, &payload).unwrap();
+        let dec = decode(&blocks[..3]).unwrap();
+        assert_eq!(dec.failed, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_unconfirmed() {
+        let header = Header::new(Format::Unconfirmed, 0x3f, 0xabcdef);
+
+        for &len in [0, 8, 9, 20, 21, 100].iter() {
+            let payload = payload(len);
+            let blocks = encode(&header, &payload).unwrap();
+
+            assert_eq!(blocks.len(), 1 + (len + 4).div_ceil(12));
+
+            let dec = decode(&blocks).unwrap();
+            assert_eq!(dec.header.format, Format::Unconfirmed);
+            assert_eq!(dec.payload, payload);
+            assert!(dec.failed.is_empty());
+            assert!(dec.crc_ok);
+        }
+
+        let payload = payload(30);
+        let mut blocks = encode(&header, &payload).unwrap();
+        blocks[2][10] = Dibit::new(blocks[2][10].bits() ^ 0b01);
+
+        let dec = decode(&blocks).unwrap();
+        assert_eq!(dec.payload, payload);
+        assert!(dec.crc_ok);
+
+        assert_eq!(
+            decode::<Dibit>(&[]).map(|d| d.payload),
+            Err(DecodeError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_packet_crc() {
+        // The packet CRC covers the payload and pad bytes.
+        let header = Header::new(Format::Unconfirmed, 0, 0);
+        let payload = payload(5);
+        let blocks = encode(&header, &payload).unwrap();
+
+        let (bytes, _) = data_block::decode_half(&blocks[1]);
+        let mut data = payload.clone();
+        data.resize(8, 0);
+
+        assert_eq!(&bytes[..8], &data[..]);
+        assert_eq!(&bytes[8..], &CRC_32.checksum(&data).to_be_bytes());
+    }
+}
@@ -0,0 +1,610 @@
+//! Channel simulation for measuring the error rates of the block codes.
+//!
+//! Frames of random data are encoded with a `BlockCode`, transmitted bit-by-bit over a
+//! simulated `Channel`, and decoded, and the resulting bit, frame, and undetected error
+//! rates are collected. Sweeping the channel parameter (crossover probability or SNR)
+//! produces the points of a waterfall curve, which can be written out as a table or CSV.
+//!
+//! All randomness comes from a seeded generator, so runs are reproducible.
+//!
+//! ```
+//! use code_rs::coding::{block::BlockCode, golay};
+//! use code_rs::sim::{csv, sweep, Awgn};
+//!
+//! let rate = golay::standard::Code::K as f64 / golay::standard::Code::N as f64;
+//! let points = sweep::<golay::standard::Code, _, _>(
+//!     &[0.0, 2.0, 4.0],
+//!     |snr| Awgn::bpsk(snr, rate),
+//!     100,
+//!     42,
+//! );
+//!
+//! assert!(csv(&points).starts_with("param,frames,"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::bits::Hexbit;
+use crate::coding::block::BlockCode;
+
+/// Seeded pseudorandom number generator (xorshift64*).
+#[derive(Copy, Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Construct a new `Rng` from the given seed. Equal seeds produce equal sequences.
+    pub fn new(seed: u64) -> Rng {
+        // Scramble the seed (splitmix64) so similar seeds give unrelated sequences and a
+        // zero seed doesn't produce the stuck zero state.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        Rng(if z == 0 { 1 } else { z })
+    }
+
+    /// Generate the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Generate a uniform random number in [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Generate a random bit that's set with the given probability.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.uniform() < p
+    }
+
+    /// Generate a standard normal random number using the Box-Muller transform.
+    pub fn gaussian(&mut self) -> f64 {
+        // Use (0, 1] for the logarithm.
+        let u = 1.0 - self.uniform();
+        let v = self.uniform();
+
+        (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+    }
+}
+
+/// Data or codeword type of a block code that can be sent bit-by-bit over a channel.
+pub trait Word: Copy + PartialEq {
+    /// Number of transmitted bits in a word of the given number of bits/symbols.
+    fn width(len: usize) -> usize;
+
+    /// Generate a random word of the given number of bits/symbols.
+    fn random(rng: &mut Rng, len: usize) -> Self;
+
+    /// Convert the word of the given number of bits/symbols to bits in transmission
+    /// order.
+    fn to_bits(&self, len: usize) -> Vec<bool>;
+
+    /// Convert the given bits in transmission order back to a word.
+    fn from_bits(bits: &[bool]) -> Self;
+
+    /// Count the bits that differ between the two words.
+    fn bit_errors(&self, other: &Self) -> usize;
+}
+
+macro_rules! impl_word {
+    ($type:ty) => {
+        /// Words are transmitted starting with the MSB of the low `len` bits.
+        impl Word for $type {
+            fn width(len: usize) -> usize {
+                len
+            }
+
+            fn random(rng: &mut Rng, len: usize) -> Self {
+                let bits = rng.next_u64() as $type;
+
+                if len == <$type>::BITS as usize {
+                    bits
+                } else {
+                    bits & ((1 << len) - 1)
+                }
+            }
+
+            fn to_bits(&self, len: usize) -> Vec<bool> {
+                (0..len).rev().map(|i| self >> i & 1 == 1).collect()
+            }
+
+            fn from_bits(bits: &[bool]) -> Self {
+                bits.iter().fold(0, |w, &b| w << 1 | b as $type)
+            }
+
+            fn bit_errors(&self, other: &Self) -> usize {
+                (self ^ other).count_ones() as usize
+            }
+        }
+    };
+}
+
+impl_word!(u8);
+impl_word!(u16);
+impl_word!(u32);
+impl_word!(u64);
+
+/// Hexbit words are transmitted symbol-by-symbol, starting with the MSB of each symbol.
+impl<const L: usize> Word for [Hexbit; L] {
+    fn width(len: usize) -> usize {
+        len * 6
+    }
+
+    fn random(rng: &mut Rng, _: usize) -> Self {
+        let mut word = [Hexbit::default(); L];
+
+        for h in word.iter_mut() {
+            *h = Hexbit::new(rng.next_u64() as u8 & 0b111111);
+        }
+
+        word
+    }
+
+    fn to_bits(&self, _: usize) -> Vec<bool> {
+        self.iter()
+            .flat_map(|h| (0..6).rev().map(move |i| h.bits() >> i & 1 == 1))
+            .collect()
+    }
+
+    fn from_bits(bits: &[bool]) -> Self {
+        let mut word = [Hexbit::default(); L];
+
+        for (h, chunk) in word.iter_mut().zip(bits.chunks(6)) {
+            *h = Hexbit::new(chunk.iter().fold(0, |s, &b| s << 1 | b as u8));
+        }
+
+        word
+    }
+
+    fn bit_errors(&self, other: &Self) -> usize {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| (a.bits() ^ b.bits()).count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Transmission channel that corrupts bits.
+pub trait Channel {
+    /// Transmit the given bits in order, replacing each with the received hard decision.
+    fn transmit(&mut self, rng: &mut Rng, bits: &mut [bool]);
+}
+
+/// Binary symmetric channel, which flips each bit independently with the given crossover
+/// probability.
+#[derive(Copy, Clone, Debug)]
+pub struct Bsc(pub f64);
+
+impl Channel for Bsc {
+    fn transmit(&mut self, rng: &mut Rng, bits: &mut [bool]) {
+        for b in bits.iter_mut() {
+            *b ^= rng.chance(self.0);
+        }
+    }
+}
+
+/// Modulation used on an AWGN channel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Modulation {
+    /// Antipodal signalling with one bit per symbol.
+    Bpsk,
+    /// 4-level signalling with two bits per symbol, at the P25 C4FM deviations +3 (01),
+    /// +1 (00), -1 (10), and -3 (11).
+    Fsk4,
+}
+
+/// Additive white Gaussian noise channel with hard-decision demodulation.
+#[derive(Copy, Clone, Debug)]
+pub struct Awgn {
+    /// Modulation of the transmitted bits.
+    modulation: Modulation,
+    /// Standard deviation of the noise relative to unit symbol spacing.
+    sigma: f64,
+}
+
+impl Awgn {
+    /// Construct a new `Awgn` channel with the given modulation, where `ebn0` is the
+    /// energy per data bit relative to the noise density in dB and `rate` is the code
+    /// rate k/n.
+    pub fn new(modulation: Modulation, ebn0: f64, rate: f64) -> Awgn {
+        let (energy, bits) = match modulation {
+            // Symbols at ±1.
+            Modulation::Bpsk => (1.0, 1.0),
+            // Symbols at ±1 and ±3.
+            Modulation::Fsk4 => (5.0, 2.0),
+        };
+
+        // Es/N0 = (bits per symbol)(code rate)(Eb/N0), and σ² = N0 / 2.
+        let esn0 = bits * rate * 10f64.powf(ebn0 / 10.0);
+
+        Awgn {
+            modulation,
+            sigma: (energy / (2.0 * esn0)).sqrt(),
+        }
+    }
+
+    /// Construct a new BPSK channel with the given Eb/N0 in dB and code rate.
+    pub fn bpsk(ebn0: f64, rate: f64) -> Awgn {
+        Self::new(Modulation::Bpsk, ebn0, rate)
+    }
+
+    /// Construct a new 4-FSK channel with the given Eb/N0 in dB and code rate.
+    pub fn fsk4(ebn0: f64, rate: f64) -> Awgn {
+        Self::new(Modulation::Fsk4, ebn0, rate)
+    }
+}
+
+impl Channel for Awgn {
+    fn transmit(&mut self, rng: &mut Rng, bits: &mut [bool]) {
+        match self.modulation {
+            Modulation::Bpsk => {
+                for b in bits.iter_mut() {
+                    let x = if *b { -1.0 } else { 1.0 } + self.sigma * rng.gaussian();
+                    *b = x < 0.0;
+                }
+            }
+            Modulation::Fsk4 => {
+                const LEVELS: [f64; 4] = [1.0, 3.0, -1.0, -3.0];
+
+                // An odd final bit is sent as the high bit of a padded dibit.
+                for pair in bits.chunks_mut(2) {
+                    let hi = pair[0] as usize;
+                    let lo = pair.get(1).map_or(0, |&b| b as usize);
+
+                    let x = LEVELS[hi << 1 | lo] + self.sigma * rng.gaussian();
+
+                    let (hi, lo) = if x >= 2.0 {
+                        (false, true)
+                    } else if x >= 0.0 {
+                        (false, false)
+                    } else if x >= -2.0 {
+                        (true, false)
+                    } else {
+                        (true, true)
+                    };
+
+                    pair[0] = hi;
+
+                    if let Some(b) = pair.get_mut(1) {
+                        *b = lo;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Burst error channel, where a burst starts at each bit with the given probability and
+/// randomizes the given number of bits.
+#[derive(Copy, Clone, Debug)]
+pub struct Burst {
+    /// Probability a burst starts at any bit.
+    pub rate: f64,
+    /// Number of bits covered by each burst.
+    pub len: usize,
+}
+
+impl Channel for Burst {
+    fn transmit(&mut self, rng: &mut Rng, bits: &mut [bool]) {
+        let mut remain = 0;
+
+        for b in bits.iter_mut() {
+            if remain == 0 && rng.chance(self.rate) {
+                remain = self.len;
+            }
+
+            if remain > 0 {
+                *b ^= rng.chance(0.5);
+                remain -= 1;
+            }
+        }
+    }
+}
+
+/// Error statistics collected over a number of frames.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of frames sent.
+    pub frames: usize,
+    /// Number of data bits sent.
+    pub bits: usize,
+    /// Number of data bits in error, where every data bit of a frame that failed to
+    /// decode is counted as in error.
+    pub bit_errors: usize,
+    /// Number of frames that failed to decode or decoded to the wrong data.
+    pub frame_errors: usize,
+    /// Number of frames that decoded successfully to the wrong data.
+    pub undetected: usize,
+}
+
+impl Stats {
+    /// Bit error rate of the data.
+    pub fn ber(&self) -> f64 {
+        ratio(self.bit_errors, self.bits)
+    }
+
+    /// Frame error rate.
+    pub fn fer(&self) -> f64 {
+        ratio(self.frame_errors, self.frames)
+    }
+
+    /// Rate of frames with undetected errors.
+    pub fn undetected_rate(&self) -> f64 {
+        ratio(self.undetected, self.frames)
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        0.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
+/// Send the given number of random frames through the given code and channel.
+pub fn run<C, H>(channel: &mut H, rng: &mut Rng, frames: usize) -> Stats
+where
+    C: BlockCode,
+    C::Data: Word,
+    C::Codeword: Word,
+    H: Channel,
+{
+    let mut stats = Stats::default();
+
+    for _ in 0..frames {
+        let data = C::Data::random(rng, C::K);
+
+        let mut bits = C::encode(data).to_bits(C::N);
+        channel.transmit(rng, &mut bits);
+
+        let width = C::Data::width(C::K);
+
+        stats.frames += 1;
+        stats.bits += width;
+
+        match C::decode(C::Codeword::from_bits(&bits)) {
+            Ok((dec, _)) if dec == data => {}
+            Ok((dec, _)) => {
+                stats.bit_errors += dec.bit_errors(&data);
+                stats.frame_errors += 1;
+                stats.undetected += 1;
+            }
+            Err(_) => {
+                stats.bit_errors += width;
+                stats.frame_errors += 1;
+            }
+        }
+    }
+
+    stats
+}
+
+/// Measurement at a single channel parameter.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point {
+    /// Channel parameter, such as the crossover probability or SNR.
+    pub param: f64,
+    /// Statistics measured at the parameter.
+    pub stats: Stats,
+}
+
+/// Run the given number of frames at each of the given channel parameters, building each
+/// channel with the given function. The generator is seeded once from the given seed, so
+/// the whole sweep is reproducible.
+pub fn sweep<C, H, F>(params: &[f64], mut channel: F, frames: usize, seed: u64) -> Vec<Point>
+where
+    C: BlockCode,
+    C::Data: Word,
+    C::Codeword: Word,
+    H: Channel,
+    F: FnMut(f64) -> H,
+{
+    let mut rng = Rng::new(seed);
+
+    params
+        .iter()
+        .map(|&param| Point {
+            param,
+            stats: run::<C, H>(&mut channel(param), &mut rng, frames),
+        })
+        .collect()
+}
+
+/// Format the given points as an aligned text table.
+pub fn table(points: &[Point]) -> String {
+    let mut out = format!(
+        "{:>12} {:>10} {:>12} {:>12} {:>12}\n",
+        "param", "frames", "ber", "fer", "undetected"
+    );
+
+    for p in points {
+        writeln!(
+            out,
+            "{:>12.6} {:>10} {:>12.4e} {:>12.4e} {:>12.4e}",
+            p.param,
+            p.stats.frames,
+            p.stats.ber(),
+            p.stats.fer(),
+            p.stats.undetected_rate()
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// Format the given points as CSV, with a header row.
+pub fn csv(points: &[Point]) -> String {
+    let mut out = String::from(
+        "param,frames,bits,bit_errors,frame_errors,undetected,ber,fer,undetected_rate\n",
+    );
+
+    for p in points {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:e},{:e},{:e}",
+            p.param,
+            p.stats.frames,
+            p.stats.bits,
+            p.stats.bit_errors,
+            p.stats.frame_errors,
+            p.stats.undetected,
+            p.stats.ber(),
+            p.stats.fer(),
+            p.stats.undetected_rate()
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coding::{bch, cyclic, golay, hamming, reed_solomon};
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        for _ in 0..100 {
+            let x = a.next_u64();
+            assert_eq!(x, b.next_u64());
+            assert_ne!(x, c.next_u64());
+        }
+
+        let n = 20000;
+        let (sum, sq) = (0..n).fold((0.0, 0.0), |(s, q), _| {
+            let x = a.gaussian();
+            (s + x, q + x * x)
+        });
+
+        assert!((sum / n as f64).abs() < 0.05);
+        assert!((sq / n as f64 - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_word() {
+        let w: u16 = 0b1011_0000_0000_0001;
+        assert_eq!(w.to_bits(16)[..4], [true, false, true, true]);
+        assert_eq!(u16::from_bits(&w.to_bits(16)), w);
+        assert_eq!(u16::from_bits(&0b101u16.to_bits(15)), 0b101);
+
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            assert_eq!(u16::random(&mut rng, 11) >> 11, 0);
+        }
+
+        let h = [Hexbit::new(0b100001), Hexbit::new(0b000011)];
+        let bits = h.to_bits(2);
+        assert_eq!(bits.len(), 12);
+        assert!(bits[0] && bits[5] && bits[10] && bits[11]);
+        assert_eq!(<[Hexbit; 2]>::from_bits(&bits), h);
+        assert_eq!(h.bit_errors(&[Hexbit::new(0), Hexbit::new(0)]), 4);
+    }
+
+    #[test]
+    fn test_channels() {
+        let mut rng = Rng::new(3);
+
+        let mut bits = vec![false; 10000];
+        Bsc(0.0).transmit(&mut rng, &mut bits);
+        assert!(bits.iter().all(|&b| !b));
+
+        Bsc(0.1).transmit(&mut rng, &mut bits);
+        let flips = bits.iter().filter(|&&b| b).count();
+        assert!(flips > 800 && flips < 1200);
+
+        for &modulation in [Modulation::Bpsk, Modulation::Fsk4].iter() {
+            let mut bits: Vec<bool> = (0..10001).map(|i| i % 3 == 0).collect();
+            let orig = bits.clone();
+            Awgn::new(modulation, 30.0, 1.0).transmit(&mut rng, &mut bits);
+            assert_eq!(bits, orig);
+
+            Awgn::new(modulation, 0.0, 1.0).transmit(&mut rng, &mut bits);
+            assert_ne!(bits, orig);
+        }
+
+        let mut bits = vec![false; 1000];
+        Burst { rate: 0.0, len: 8 }.transmit(&mut rng, &mut bits);
+        assert!(bits.iter().all(|&b| !b));
+
+        let mut bits = vec![false; 1000];
+        Burst {
+            rate: 1.0,
+            len: 1000,
+        }
+        .transmit(&mut rng, &mut bits);
+        let flips = bits.iter().filter(|&&b| b).count();
+        assert!(flips > 400 && flips < 600);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut rng = Rng::new(5);
+
+        // Noiseless channel.
+        let stats = run::<bch::Code, _>(&mut Bsc(0.0), &mut rng, 10);
+        assert_eq!(
+            stats,
+            Stats {
+                frames: 10,
+                bits: 160,
+                ..Stats::default()
+            }
+        );
+
+        // Hamming codes correct every single error but miscorrect most double errors.
+        let stats = run::<hamming::standard::Code, _>(&mut Bsc(0.2), &mut rng, 1000);
+        assert!(stats.frame_errors > 0);
+        assert!(stats.undetected > 0);
+        assert!(stats.bit_errors <= stats.frame_errors * 11);
+
+        // Bursts shorter than a hexbit symbol are fixed by the Reed-Solomon code.
+        let stats = run::<reed_solomon::long::Code, _>(
+            &mut Burst {
+                rate: 1.0 / 216.0,
+                len: 3,
+            },
+            &mut rng,
+            200,
+        );
+        assert!(stats.fer() < 0.05);
+
+        let stats = run::<golay::extended::Code, _>(&mut Awgn::fsk4(10.0, 0.5), &mut rng, 200);
+        assert_eq!(stats.frame_errors, 0);
+    }
+
+    #[test]
+    fn test_sweep() {
+        let points = sweep::<cyclic::Code, _, _>(&[0.0, 0.01, 0.3], Bsc, 500, 9);
+
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].stats.frame_errors, 0);
+        assert!(points[1].stats.fer() < points[2].stats.fer());
+        assert_eq!(
+            points,
+            sweep::<cyclic::Code, _, _>(&[0.0, 0.01, 0.3], Bsc, 500, 9)
+        );
+
+        let csv = csv(&points);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("param,frames,bits,bit_errors,frame_errors,undetected,ber,fer,undetected_rate")
+        );
+        assert!(lines.next().unwrap().starts_with("0,500,4000,0,0,0,"));
+        assert_eq!(lines.count(), 2);
+
+        assert_eq!(table(&points).lines().count(), 4);
+    }
+}
@@ -0,0 +1,183 @@
+//! Encoding and decoding of the link control (LC) word carried by the P25 terminator data
+//! unit with link control (TDULC), protected by the (24, 12, 13) Reed-Solomon code, with
+//! each pair of hexbits further protected by the (24, 12, 8) extended Golay code.
+
+use crate::bits::{Hexbit, HexbitBytes, Hexbits};
+use crate::coding::{golay, reed_solomon};
+use crate::error::DecodeError;
+use crate::ldu1::LinkControl;
+
+/// Number of Golay-coded words in the TDULC.
+pub const WORDS: usize = 12;
+
+/// Link control carried by the TDULC.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tdulc {
+    /// Link control word.
+    pub lc: LinkControl,
+}
+
+/// Errors corrected at each coding layer while decoding a TDULC.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Errors {
+    /// Number of bits corrected by the Golay code in each word, or `None` if the word
+    /// couldn't be decoded and both of its hexbits were passed to the Reed-Solomon
+    /// decoder as erasures.
+    pub golay: [Option<usize>; WORDS],
+    /// Number of hexbits corrected by the Reed-Solomon code, including filled erasures.
+    pub rs: usize,
+}
+
+impl Errors {
+    /// Total number of bits corrected by the Golay code across all decoded words.
+    pub fn golay_bits(&self) -> usize {
+        self.golay.iter().flatten().sum()
+    }
+
+    /// Number of Golay words that were erased.
+    pub fn erased(&self) -> usize {
+        self.golay.iter().filter(|err| err.is_none()).count()
+    }
+}
+
+impl Tdulc {
+    /// Create a new TDULC carrying the given LC.
+    pub fn new(lc: LinkControl) -> Tdulc {
+        Tdulc { lc }
+    }
+
+    /// Encode the LC into the 12 24-bit Golay codewords of the TDULC, in transmission
+    /// order, for 288 bits in total.
+    pub fn encode(&self) -> [u32; WORDS] {
+        let bytes = self.lc.to_bytes();
+        let mut buf = [Hexbit::default(); 24];
+
+        for (dest, hexbit) in buf.iter_mut().zip(Hexbits::new(bytes.iter().cloned())) {
+            *dest = hexbit;
+        }
+
+        reed_solomon::short::encode(&mut buf);
+
+        let mut words = [0; WORDS];
+
+        for (word, pair) in words.iter_mut().zip(buf.chunks(2)) {
+            *word = golay::extended::encode((pair[0].bits() as u16) << 6 | pair[1].bits() as u16);
+        }
+
+        words
+    }
+
+    /// Try to decode the given 12 24-bit words to the nearest TDULC. Both hexbits of a
+    /// word the Golay code can't correct are treated as erasures by the Reed-Solomon
+    /// code, so any combination of e hexbit errors and f failed words with 2e + 2f ≤ 12
+    /// can be corrected.
+    ///
+    /// If decoding was successful, return `Ok((tdulc, errs))`, where `tdulc` is the
+    /// decoded TDULC and `errs` reports the errors corrected in each Golay word and by
+    /// the Reed-Solomon code. Otherwise, return an `Err` describing the unrecoverable
+    /// error.
+    pub fn decode(words: &[u32; WORDS]) -> Result<(Tdulc, Errors), DecodeError> {
+        let mut buf = [Hexbit::default(); 24];
+        let mut erasures = Vec::new();
+        let mut golay = [None; WORDS];
+
+        for (i, (pair, &word)) in buf.chunks_mut(2).zip(words.iter()).enumerate() {
+            match golay::extended::decode(word) {
+                Ok((data, err)) => {
+                    pair[0] = Hexbit::new((data >> 6) as u8);
+                    pair[1] = Hexbit::new(data as u8 & 0b111111);
+                    golay[i] = Some(err);
+                }
+                Err(_) => erasures.extend_from_slice(&[i * 2, i * 2 + 1]),
+            }
+        }
+
+        let (data, rs) = reed_solomon::short::decode_with_erasures(&mut buf, &erasures)?;
+
+        let mut bytes = [0; 9];
+
+        for (dest, byte) in bytes.iter_mut().zip(HexbitBytes::new(data.iter().cloned())) {
+            *dest = byte;
+        }
+
+        Ok((
+            Tdulc::new(LinkControl::from_bytes(&bytes)),
+            Errors { golay, rs },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lc() -> LinkControl {
+        LinkControl {
+            lcf: 0x0f,
+            mfid: 0x00,
+            data: [0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0xd2],
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let words = Tdulc::default().encode();
+        assert!(words.iter().all(|&w| w == 0));
+
+        let t = Tdulc::new(lc());
+        let words = t.encode();
+        assert!(words.iter().all(|&w| w >> 24 == 0));
+
+        // Each word carries 12 bits of the LC, MSB first.
+        assert_eq!(words[0], golay::extended::encode(0x0f0));
+        assert_eq!(words[5], golay::extended::encode(0x4d2));
+
+        assert_eq!(
+            Tdulc::decode(&words),
+            Ok((
+                t,
+                Errors {
+                    golay: [Some(0); WORDS],
+                    rs: 0
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let t = Tdulc::new(lc());
+        let words = t.encode();
+
+        let mut recv = words;
+        recv[0] ^= 0b111 << 20;
+        recv[4] ^= 1;
+        recv[11] = golay::extended::encode((recv[11] >> 12) as u16 ^ 0b1000001);
+
+        let (dec, errs) = Tdulc::decode(&recv).unwrap();
+        assert_eq!(dec, t);
+        assert_eq!(errs.golay[0], Some(3));
+        assert_eq!(errs.golay[4], Some(1));
+        assert_eq!(errs.golay[11], Some(0));
+        assert_eq!(errs.golay_bits(), 4);
+        assert_eq!(errs.erased(), 0);
+        assert_eq!(errs.rs, 2);
+
+        // Words with 4 errors are detected by the Golay code and erased.
+        let mut recv = words;
+        for i in 0..6 {
+            recv[i * 2] ^= 0b1111;
+        }
+
+        let (dec, errs) = Tdulc::decode(&recv).unwrap();
+        assert_eq!(dec, t);
+        for (i, err) in errs.golay.iter().enumerate() {
+            assert_eq!(*err, if i % 2 == 0 { None } else { Some(0) });
+        }
+        assert_eq!(errs.erased(), 6);
+        assert_eq!(errs.golay_bits(), 0);
+
+        recv[1] ^= 0b1111;
+        assert_eq!(Tdulc::decode(&recv), Err(DecodeError::TooManyErasures));
+    }
+}
@@ -0,0 +1,126 @@
+//! Encoding and decoding of the P25 trunking signalling block (TSBK), which carries 10
+//! bytes of trunking data and a 16-bit CRC-CCITT protected by the 1/2-rate trellis code
+//! and spread over the block by the 98-dibit data interleaver.
+
+use crate::bits::Dibit;
+use crate::coding::crc::CRC_CCITT;
+use crate::coding::interleave::p25_data;
+use crate::coding::trellis::DibitMetric;
+use crate::data_block::{self, HALF_BYTES};
+
+/// Number of data bytes in a TSBK, excluding the CRC.
+pub const BYTES: usize = 10;
+
+/// Number of transmitted dibits in a TSBK.
+pub const DIBITS: usize = p25_data::DIBITS;
+
+/// Decoded TSBK.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// Decoded data bytes, excluding the CRC.
+    pub data: [u8; BYTES],
+    /// Whether the CRC calculated over the decoded data matched the decoded CRC.
+    pub crc_ok: bool,
+    /// Index of each decoded dibit symbol the Viterbi decoder couldn't decide between
+    /// equally likely values, which is filled with zero.
+    pub ambiguous: Vec<usize>,
+}
+
+/// Encode the given data bytes into the 98 interleaved dibits of a TSBK, appending the
+/// CRC and trellis coding the result.
+pub fn encode(data: &[u8; BYTES]) -> [Dibit; DIBITS] {
+    let crc = CRC_CCITT.checksum(data);
+
+    let mut bytes = [0; HALF_BYTES];
+    bytes[..BYTES].copy_from_slice(data);
+    bytes[BYTES] = (crc >> 8) as u8;
+    bytes[BYTES + 1] = crc as u8;
+
+    data_block::encode_half(&bytes)
+}
+
+/// Decode the given 98 interleaved received dibits, which may be hard `Dibit` decisions
+/// or any soft-decision type implementing `DibitMetric`, into the TSBK data bytes.
+///
+/// Decoding always produces data bytes, and the returned `Decoded` reports whether the
+/// CRC passed and which symbols were ambiguous.
+pub fn decode<M: DibitMetric + Copy>(dibits: &[M; DIBITS]) -> Decoded {
+    let (bytes, ambiguous) = data_block::decode_half(dibits);
+
+    let mut data = [0; BYTES];
+    data.copy_from_slice(&bytes[..BYTES]);
+
+    let crc = (bytes[BYTES] as u32) << 8 | bytes[BYTES + 1] as u32;
+
+    Decoded {
+        data,
+        crc_ok: CRC_CCITT.checksum(&data) == crc,
+        ambiguous,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coding::trellis::{Deviation, DibitLlr};
+
+    const DATA: [u8; BYTES] = [0xbd, 0x00, 0x00, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde];
+
+    #[test]
+    fn test_roundtrip() {
+        let dibits = encode(&DATA);
+
+        assert_eq!(
+            decode(&dibits),
+            Decoded {
+                data: DATA,
+                crc_ok: true,
+                ambiguous: vec![],
+            }
+        );
+
+        // Spread errors are corrected by the trellis code.
+        let mut recv = dibits;
+        for i in [3, 30, 60, 90].iter() {
+            recv[*i] = Dibit::new(recv[*i].bits() ^ 0b11);
+        }
+
+        let dec = decode(&recv);
+        assert_eq!(dec.data, DATA);
+        assert!(dec.crc_ok);
+    }
+
+    #[test]
+    fn test_soft() {
+        let dibits = encode(&DATA);
+
+        let mut soft = [Deviation(0.0); DIBITS];
+        for (dest, dibit) in soft.iter_mut().zip(dibits.iter()) {
+            *dest = Deviation([1.0, 3.0, -1.0, -3.0][dibit.bits() as usize] * 0.8);
+        }
+
+        // Push one symbol just over the decision boundary.
+        soft[10] = Deviation(if soft[10].0 > 0.0 { -0.1 } else { 0.1 });
+
+        let dec = decode(&soft);
+        assert_eq!(dec.data, DATA);
+        assert!(dec.crc_ok);
+    }
+
+    #[test]
+    fn test_crc_fail() {
+        // A valid trellis codeword whose CRC doesn't match.
+        let mut bytes = [0x12; HALF_BYTES];
+        bytes[..BYTES].copy_from_slice(&DATA);
+        let dibits = data_block::encode_half(&bytes);
+
+        let dec = decode(&dibits);
+        assert_eq!(dec.data, DATA);
+        assert!(!dec.crc_ok);
+        assert!(dec.ambiguous.is_empty());
+
+        // Symbols with no information are ambiguous.
+        let dec = decode(&[DibitLlr(0.0, 0.0); DIBITS]);
+        assert!(!dec.ambiguous.is_empty());
+    }
+}